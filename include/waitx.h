@@ -0,0 +1,219 @@
+#ifndef WAITX_H
+#define WAITX_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdbool.h>
+
+/**
+ * Receiving half of a channel of `void*` values.
+ */
+typedef struct WaitxReceiver WaitxReceiver;
+
+/**
+ * Sending half of a channel of `void*` values.
+ */
+typedef struct WaitxSender WaitxSender;
+
+/**
+ * Waiting half of a counted notification pair.
+ */
+typedef struct WaitxWaiter WaitxWaiter;
+
+/**
+ * Signaling half of a counted notification pair.
+ */
+typedef struct WaitxWaker WaitxWaker;
+
+/**
+ * Creates a new single-slot channel, storing its endpoints in `tx` and `rx`.
+ *
+ * # Safety
+ *
+ * `tx` and `rx` must be valid for writes.
+ */
+void waitx_channel_new(struct WaitxSender **tx, struct WaitxReceiver **rx);
+
+/**
+ * Sends `value`, blocking until the slot is empty.
+ *
+ * Aborts if the channel is closed, which freeing the receiver does too; see
+ * [`waitx_send_unless_closed`].
+ *
+ * # Safety
+ *
+ * `tx` must be a live sender from [`waitx_channel_new`], used by one thread
+ * at a time.
+ */
+void waitx_send(const struct WaitxSender *tx, void *value);
+
+/**
+ * Sends `value`, blocking until the slot is empty. Returns `false` if the
+ * channel is or gets closed first, e.g. because the receiver was freed.
+ *
+ * # Safety
+ *
+ * `tx` must be a live sender from [`waitx_channel_new`], used by one thread
+ * at a time.
+ */
+bool waitx_send_unless_closed(const struct WaitxSender *tx, void *value);
+
+/**
+ * Sends `value` if the slot is empty. Returns `false` if it is full or the
+ * channel closed.
+ *
+ * # Safety
+ *
+ * `tx` must be a live sender from [`waitx_channel_new`], used by one thread
+ * at a time.
+ */
+bool waitx_try_send(const struct WaitxSender *tx, void *value);
+
+/**
+ * Receives a value, blocking until one is available.
+ *
+ * Aborts if the channel is closed, which freeing the sender does too, and
+ * no value is left; see [`waitx_recv_unless_closed`].
+ *
+ * # Safety
+ *
+ * `rx` must be a live receiver from [`waitx_channel_new`], used by one thread
+ * at a time.
+ */
+void *waitx_recv(const struct WaitxReceiver *rx);
+
+/**
+ * Receives a value into `out`, blocking until one is available. Returns
+ * `false` once the channel is closed and empty, e.g. because the sender
+ * was freed, leaving `out` untouched.
+ *
+ * # Safety
+ *
+ * `rx` must be a live receiver from [`waitx_channel_new`], used by one thread
+ * at a time, and `out` must be valid for writes.
+ */
+bool waitx_recv_unless_closed(const struct WaitxReceiver *rx, void **out);
+
+/**
+ * Receives a value into `out` if one is available. Returns `false` if the
+ * slot is empty, leaving `out` untouched.
+ *
+ * # Safety
+ *
+ * `rx` must be a live receiver from [`waitx_channel_new`], used by one thread
+ * at a time, and `out` must be valid for writes.
+ */
+bool waitx_try_recv(const struct WaitxReceiver *rx, void **out);
+
+/**
+ * Closes the channel: a value still in the slot can be received, then
+ * receives fail. A blocked receiver is woken.
+ *
+ * # Safety
+ *
+ * `tx` must be a live sender from [`waitx_channel_new`].
+ */
+void waitx_sender_close(const struct WaitxSender *tx);
+
+/**
+ * Closes the channel: a value still in the slot can be received, then
+ * sends fail. A blocked sender is woken.
+ *
+ * # Safety
+ *
+ * `rx` must be a live receiver from [`waitx_channel_new`].
+ */
+void waitx_receiver_close(const struct WaitxReceiver *rx);
+
+/**
+ * Releases a sender, closing the channel. Passing `NULL` is a no-op.
+ *
+ * # Safety
+ *
+ * `tx` must be `NULL` or a sender from [`waitx_channel_new`] not yet freed.
+ */
+void waitx_sender_free(struct WaitxSender *tx);
+
+/**
+ * Releases a receiver, closing the channel. Passing `NULL` is a no-op.
+ *
+ * # Safety
+ *
+ * `rx` must be `NULL` or a receiver from [`waitx_channel_new`] not yet freed.
+ */
+void waitx_receiver_free(struct WaitxReceiver *rx);
+
+/**
+ * Creates a new counted notification pair, storing its halves in `waker`
+ * and `waiter`.
+ *
+ * # Safety
+ *
+ * `waker` and `waiter` must be valid for writes.
+ */
+void waitx_pair_new(struct WaitxWaker **waker, struct WaitxWaiter **waiter);
+
+/**
+ * Returns a new waker signaling the same waiter.
+ *
+ * # Safety
+ *
+ * `waker` must be a live waker.
+ */
+struct WaitxWaker *waitx_waker_clone(const struct WaitxWaker *waker);
+
+/**
+ * Increments the event counter and wakes the waiting thread.
+ *
+ * # Safety
+ *
+ * `waker` must be a live waker.
+ */
+void waitx_waker_signal(const struct WaitxWaker *waker);
+
+/**
+ * Wakes the waiter only if it is currently blocked.
+ *
+ * # Safety
+ *
+ * `waker` must be a live waker.
+ */
+void waitx_waker_poke(const struct WaitxWaker *waker);
+
+/**
+ * Blocks until the next notification.
+ *
+ * # Safety
+ *
+ * `waiter` must be a live waiter.
+ */
+void waitx_waiter_wait(const struct WaitxWaiter *waiter);
+
+/**
+ * Consumes a notification if one is pending. Returns `false` otherwise.
+ *
+ * # Safety
+ *
+ * `waiter` must be a live waiter.
+ */
+bool waitx_waiter_try_wait(const struct WaitxWaiter *waiter);
+
+/**
+ * Releases a waker. Passing `NULL` is a no-op.
+ *
+ * # Safety
+ *
+ * `waker` must be `NULL` or a waker not yet freed.
+ */
+void waitx_waker_free(struct WaitxWaker *waker);
+
+/**
+ * Releases a waiter. Passing `NULL` is a no-op.
+ *
+ * # Safety
+ *
+ * `waiter` must be `NULL` or a waiter not yet freed.
+ */
+void waitx_waiter_free(struct WaitxWaiter *waiter);
+
+#endif  /* WAITX_H */
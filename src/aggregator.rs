@@ -0,0 +1,227 @@
+//! A many-producer, single-consumer channel built from per-producer slots.
+//!
+//! A contended multi-producer queue makes every producer fight over the same
+//! cache lines. [`channel`] instead gives each producer a slot of its own,
+//! like a single-slot [`channel`](crate::channel()), and shares only a
+//! doorbell: an event every send rings once. The consumer waits on the
+//! doorbell and then takes the value from a shard that has one, reporting
+//! which. Once every producer is dropped, the consumer receives what is left
+//! and then gets [`Closed`].
+//!
+//! # Example
+//!
+//! ```
+//! let (producers, consumer) = waitx::aggregator::channel::<u32>(2);
+//!
+//! for (i, producer) in producers.into_iter().enumerate() {
+//!     std::thread::spawn(move || producer.send(i as u32 * 10));
+//! }
+//! let mut got = [consumer.recv(), consumer.recv()];
+//! got.sort();
+//! assert_eq!(got, [(0, 0), (1, 10)]);
+//! ```
+
+use crate::prelude::*;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cell::Cell;
+
+/// One producer's slot.
+struct Shard<T> {
+    slot: Slot<T>,
+    /// Signaled by the consumer once the slot is empty.
+    empty: Event,
+    sending: ThreadTag,
+}
+
+/// The state all producers and the consumer share.
+struct Shared<T> {
+    shards: Box<[Shard<T>]>,
+    /// Signaled by a producer once its slot is full.
+    doorbell: Event,
+    receiving: ThreadTag,
+    /// Producers not dropped yet.
+    producers: AtomicUsize,
+    /// Set once the last producer is dropped.
+    closed: AtomicBool,
+}
+
+/// Sending side of an aggregator [`channel`], one per shard.
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+    shard: usize,
+    state: WaitState,
+}
+
+impl<T> Producer<T> {
+    /// Sends a value, blocking until the consumer took this producer's
+    /// previous one.
+    #[inline]
+    pub fn send(&self, value: T) {
+        let shard = &self.shared.shards[self.shard];
+        let _use = shard.sending.enter("Producer");
+        self.state.wait(&shard.empty);
+        self.publish(shard, value);
+    }
+
+    /// Attempts to send a value without blocking, returning it if the
+    /// consumer hasn't taken this producer's previous one yet.
+    #[inline]
+    pub fn try_send(&self, value: T) -> Result<(), T> {
+        let shard = &self.shared.shards[self.shard];
+        let _use = shard.sending.enter("Producer");
+        if !self.state.try_wait(&shard.empty) {
+            return Err(value);
+        }
+        self.publish(shard, value);
+        Ok(())
+    }
+
+    /// Returns the index of this producer's shard, as reported by
+    /// [`Consumer::recv`].
+    pub fn shard(&self) -> usize {
+        self.shard
+    }
+
+    #[inline(always)]
+    fn publish(&self, shard: &Shard<T>, value: T) {
+        // SAFETY: consuming `empty` handed us the shard's slot, and it is
+        // empty.
+        unsafe {
+            shard.slot.write(value);
+        }
+        shard.slot.mark_full();
+        self.shared.doorbell.signal();
+    }
+}
+
+impl<T> Drop for Producer<T> {
+    fn drop(&mut self) {
+        if self.shared.producers.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.shared.closed.store(true, Ordering::SeqCst);
+            self.shared.doorbell.signal_n(0);
+        }
+    }
+}
+
+/// Receiving side of an aggregator [`channel`].
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+    state: WaitState,
+    /// Shard the next scan starts at, so busy producers can't starve others.
+    cursor: Cell<usize>,
+}
+
+impl<T> Consumer<T> {
+    /// Receives a value from any producer, blocking until one is available,
+    /// and returns it along with the producer's shard index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if every producer was dropped and no value is left, see
+    /// [`recv_unless_closed`](Self::recv_unless_closed).
+    #[inline]
+    pub fn recv(&self) -> (usize, T) {
+        self.recv_unless_closed()
+            .unwrap_or_else(|_| panic!("waitx: recv on a closed channel"))
+    }
+
+    /// Receives a value from any producer, blocking until one is available,
+    /// or returns [`Closed`] once every producer was dropped and no value is
+    /// left.
+    #[inline]
+    pub fn recv_unless_closed(&self) -> Result<(usize, T), Closed> {
+        let _use = self.shared.receiving.enter("Consumer");
+        let shared = &*self.shared;
+        let closed = || shared.closed.load(Ordering::Acquire);
+        if !self
+            .state
+            .wait_unless(&shared.doorbell, self.state.tuning(), closed)
+        {
+            return Err(Closed(()));
+        }
+        Ok(self.take())
+    }
+
+    /// Receives a value from any producer if one is available, without
+    /// blocking.
+    #[inline]
+    pub fn try_recv(&self) -> Option<(usize, T)> {
+        let _use = self.shared.receiving.enter("Consumer");
+        if !self.state.try_wait(&self.shared.doorbell) {
+            return None;
+        }
+        Some(self.take())
+    }
+
+    /// Returns whether every producer was dropped. Values they sent before
+    /// may still be waiting to be received.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.shared.closed.load(Ordering::Acquire)
+    }
+
+    /// Returns how many producers the channel has.
+    pub fn shards(&self) -> usize {
+        self.shared.shards.len()
+    }
+
+    /// Takes the value out of the next full shard.
+    ///
+    /// Must only be called after consuming a doorbell signal: every signal
+    /// stands for one full slot, so there is one to find.
+    fn take(&self) -> (usize, T) {
+        let shards = &self.shared.shards;
+        let start = self.cursor.get();
+        let index = (0..shards.len())
+            .map(|i| (start + i) % shards.len())
+            .find(|&i| shards[i].slot.is_full())
+            .expect("a doorbell signal without a full shard");
+        self.cursor.set((index + 1) % shards.len());
+
+        let shard = &shards[index];
+        // SAFETY: the slot is full, so its producer handed it over and
+        // won't touch it before `empty` is signaled.
+        let value = unsafe { shard.slot.read() };
+        shard.slot.mark_empty();
+        shard.empty.signal();
+        (index, value)
+    }
+}
+
+/// Creates an aggregator channel with `producers` shards, returning one
+/// [`Producer`] per shard, in order, and the [`Consumer`].
+///
+/// Blocking operations use the calling thread's tuning, like
+/// [`channel`](crate::channel()).
+pub fn channel<T>(producers: usize) -> (Vec<Producer<T>>, Consumer<T>) {
+    let shared = Arc::new(Shared {
+        shards: (0..producers)
+            .map(|_| Shard {
+                slot: Slot::default(),
+                // the slot starts empty, so the first send goes through
+                empty: Event::with_initial(1),
+                sending: ThreadTag::new(),
+            })
+            .collect(),
+        doorbell: Event::new(),
+        receiving: ThreadTag::new(),
+        producers: AtomicUsize::new(producers),
+        // without producers, there is nothing to wait for
+        closed: AtomicBool::new(producers == 0),
+    });
+    let producers = (0..producers)
+        .map(|shard| Producer {
+            shared: shared.clone(),
+            shard,
+            state: WaitState::new(TuningSource::Thread),
+        })
+        .collect();
+    let consumer = Consumer {
+        shared,
+        state: WaitState::new(TuningSource::Thread),
+        cursor: Cell::new(0),
+    };
+    (producers, consumer)
+}
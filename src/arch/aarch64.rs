@@ -0,0 +1,48 @@
+use core::arch::asm;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// Loads `wake` with `ldaxr`, arming the exclusive monitor on its cache line.
+#[inline(always)]
+fn load_exclusive(wake: &AtomicU32) -> u32 {
+    let addr: *const AtomicU32 = wake;
+    let val: u32;
+    unsafe {
+        asm!(
+            "ldaxr {val:w}, [{addr}]",
+            addr = in(reg) addr,
+            val = out(reg) val,
+            options(nostack, preserves_flags),
+        );
+    }
+    val
+}
+
+/// Busy phase built on `wfe`.
+///
+/// Each iteration snapshots `wake`, polls `f`, then re-arms the exclusive
+/// monitor and sleeps in `wfe` only if `wake` is still unchanged. Any store to
+/// the monitored line (or a `sev`) ends the sleep; the kernel's event stream
+/// bounds it otherwise.
+#[inline]
+pub fn busy_wait(f: &mut impl FnMut() -> bool, wake: &AtomicU32, iters: u32) -> bool {
+    for _ in 0..iters {
+        let val = wake.load(Ordering::Relaxed);
+        if f() {
+            return true;
+        }
+        if load_exclusive(wake) == val {
+            unsafe {
+                asm!("wfe", options(nostack, preserves_flags));
+            }
+        }
+    }
+    false
+}
+
+/// Signals an event to every core sleeping in `wfe`.
+#[inline(always)]
+pub fn notify() {
+    unsafe {
+        asm!("sev", options(nostack, preserves_flags));
+    }
+}
@@ -0,0 +1,192 @@
+//! Architecture-specific busy-wait primitives.
+//!
+//! The busy phase of [`wait_until_with_tuning`](crate::util::wait_until_with_tuning)
+//! is routed through [`busy_wait`], which picks the most efficient way of
+//! polling the wake word on the current CPU and falls back to
+//! [`core::hint::spin_loop`] everywhere else.
+//!
+//! Supported backends:
+//! - x86_64 with `WAITPKG` (`umonitor`/`umwait`), behind the `waitpkg` feature.
+//! - aarch64 `wfe`/`sev`, behind the `wfe` feature.
+//!
+//! On x86/x86_64, [`busy_wait_cycles`] bounds the busy phase by TSC ticks
+//! instead of iterations, when the TSC is invariant.
+
+#[cfg(not(feature = "loom"))]
+use crate::util::SpinStrategy;
+#[cfg(not(feature = "loom"))]
+use core::sync::atomic::AtomicU32;
+
+#[cfg(all(feature = "waitpkg", target_arch = "x86_64", not(feature = "loom")))]
+mod x86_64;
+
+#[cfg(all(feature = "wfe", target_arch = "aarch64", not(feature = "loom")))]
+mod aarch64;
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod tsc;
+
+/// Iterations between TSC reads in [`busy_wait_cycles`].
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(feature = "loom")
+))]
+const TSC_CHECK_ITERS: u32 = 8;
+
+/// TSC ticks the default busy phase aims to pause for per iteration, about
+/// one Skylake-era `pause`.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+const PAUSE_TARGET_CYCLES: u64 = 128;
+
+/// Most `spin_loop`s the default busy phase batches per iteration.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+const MAX_PAUSE_BATCH: u64 = 16;
+
+/// `spin_loop`s per default busy phase iteration; 0 until measured.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+static PAUSE_BATCH: core::sync::atomic::AtomicU8 = core::sync::atomic::AtomicU8::new(0);
+
+/// Returns how many `spin_loop`s one default busy phase iteration executes.
+///
+/// On x86, where `pause` latency differs by an order of magnitude between
+/// microarchitectures, it is measured once and enough pauses are batched for
+/// an iteration to take about [`PAUSE_TARGET_CYCLES`], keeping
+/// [`Tuning`](crate::Tuning) iteration counts comparable across CPUs.
+#[inline(always)]
+fn pause_batch() -> u32 {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        use core::sync::atomic::Ordering;
+
+        match PAUSE_BATCH.load(Ordering::Relaxed) {
+            0 => {
+                let batch =
+                    (PAUSE_TARGET_CYCLES / tsc::pause_cycles()).clamp(1, MAX_PAUSE_BATCH) as u8;
+                PAUSE_BATCH.store(batch, Ordering::Relaxed);
+                batch as u32
+            }
+            batch => batch as u32,
+        }
+    }
+
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    1
+}
+
+/// Executes `n` default busy phase pauses, see [`pause_batch`].
+#[inline(always)]
+pub fn relax(n: u32) {
+    for _ in 0..n.saturating_mul(pause_batch()) {
+        core::hint::spin_loop();
+    }
+}
+
+/// [`SpinStrategy::Backoff`] stops doubling at `2^BACKOFF_LIMIT` pauses.
+#[cfg(not(feature = "loom"))]
+const BACKOFF_LIMIT: u32 = 6;
+
+/// Polls `f` up to `iters` times, pausing between polls as `strategy` says.
+///
+/// Returns `true` as soon as `f` does. `wake` must be written by every
+/// notification so monitor-based backends observe it.
+#[cfg(not(feature = "loom"))]
+#[inline]
+pub fn busy_wait(
+    f: &mut impl FnMut() -> bool,
+    wake: &AtomicU32,
+    iters: u32,
+    strategy: SpinStrategy,
+) -> bool {
+    match strategy {
+        SpinStrategy::Pause => {}
+        SpinStrategy::Nop => return poll(f, iters, || {}),
+        SpinStrategy::Yield => return poll(f, iters, crate::park::yield_now),
+        SpinStrategy::Batched(n) => {
+            return poll(f, iters, || {
+                for _ in 0..n {
+                    core::hint::spin_loop();
+                }
+            });
+        }
+        SpinStrategy::Backoff => {
+            let mut step = 0;
+            return poll(f, iters, || {
+                for _ in 0..1u32 << step {
+                    core::hint::spin_loop();
+                }
+                if step < BACKOFF_LIMIT {
+                    step += 1;
+                }
+            });
+        }
+    }
+
+    #[cfg(all(feature = "waitpkg", target_arch = "x86_64"))]
+    if x86_64::has_waitpkg() {
+        // SAFETY: `WAITPKG` support was verified at runtime.
+        return unsafe { x86_64::busy_wait(f, wake, iters) };
+    }
+
+    #[cfg(all(feature = "wfe", target_arch = "aarch64"))]
+    {
+        aarch64::busy_wait(f, wake, iters)
+    }
+
+    #[cfg(not(all(feature = "wfe", target_arch = "aarch64")))]
+    {
+        let _ = wake;
+        poll(f, iters, crate::util::cpu_relax)
+    }
+}
+
+/// Polls `f` like [`busy_wait`] until `cycles` TSC ticks have elapsed.
+///
+/// Returns `None` without polling if the TSC isn't invariant, since its rate
+/// would then follow frequency scaling.
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(feature = "loom")
+))]
+#[inline]
+pub fn busy_wait_cycles(
+    f: &mut impl FnMut() -> bool,
+    wake: &AtomicU32,
+    cycles: u64,
+    strategy: SpinStrategy,
+) -> Option<bool> {
+    if !tsc::has_invariant_tsc() {
+        return None;
+    }
+    let start = tsc::now();
+    loop {
+        if busy_wait(f, wake, TSC_CHECK_ITERS, strategy) {
+            return Some(true);
+        }
+        if tsc::now().wrapping_sub(start) >= cycles {
+            return Some(false);
+        }
+    }
+}
+
+/// Polls `f` up to `iters` times, calling `pause` between polls.
+#[cfg(not(feature = "loom"))]
+#[inline(always)]
+fn poll(f: &mut impl FnMut() -> bool, iters: u32, mut pause: impl FnMut()) -> bool {
+    for _ in 0..iters {
+        if f() {
+            return true;
+        }
+        pause();
+    }
+    false
+}
+
+/// Wakes waiters sleeping in the busy phase after `wake` has been written.
+///
+/// A no-op on backends that observe the store to `wake` directly.
+#[cfg(not(feature = "loom"))]
+#[inline(always)]
+pub fn notify() {
+    #[cfg(all(feature = "wfe", target_arch = "aarch64"))]
+    aarch64::notify();
+}
@@ -0,0 +1,71 @@
+#[cfg(all(target_arch = "x86", not(feature = "loom")))]
+use core::arch::x86::__cpuid;
+#[cfg(all(target_arch = "x86", not(miri)))]
+use core::arch::x86::_rdtsc;
+#[cfg(all(target_arch = "x86_64", not(feature = "loom")))]
+use core::arch::x86_64::__cpuid;
+#[cfg(all(target_arch = "x86_64", not(miri)))]
+use core::arch::x86_64::_rdtsc;
+#[cfg(not(feature = "loom"))]
+use core::sync::atomic::{AtomicU8, Ordering};
+
+#[cfg(not(feature = "loom"))]
+const UNKNOWN: u8 = 0;
+#[cfg(not(feature = "loom"))]
+const UNSUPPORTED: u8 = 1;
+#[cfg(not(feature = "loom"))]
+const SUPPORTED: u8 = 2;
+
+#[cfg(not(feature = "loom"))]
+static INVARIANT: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+/// Returns whether the TSC ticks at a constant rate across P-, C- and
+/// T-states (CPUID.80000007H:EDX[8]), making it usable as a clock.
+#[cfg(not(feature = "loom"))]
+#[inline]
+pub fn has_invariant_tsc() -> bool {
+    // Miri can execute neither `cpuid` nor `rdtsc`
+    if cfg!(miri) {
+        return false;
+    }
+    match INVARIANT.load(Ordering::Relaxed) {
+        UNKNOWN => {
+            #[allow(unused_unsafe)]
+            let supported = unsafe {
+                __cpuid(0x8000_0000).eax >= 0x8000_0007 && __cpuid(0x8000_0007).edx & (1 << 8) != 0
+            };
+            let state = if supported { SUPPORTED } else { UNSUPPORTED };
+            INVARIANT.store(state, Ordering::Relaxed);
+            supported
+        }
+        state => state == SUPPORTED,
+    }
+}
+
+/// Reads the time-stamp counter.
+#[inline(always)]
+pub fn now() -> u64 {
+    // a frozen clock is enough under Miri, see `has_invariant_tsc`
+    #[cfg(miri)]
+    return 0;
+    #[cfg(not(miri))]
+    unsafe {
+        _rdtsc()
+    }
+}
+
+/// `spin_loop`s timed by [`pause_cycles`].
+const PAUSE_SAMPLES: u64 = 64;
+
+/// Measures the TSC ticks one [`core::hint::spin_loop`] takes.
+///
+/// `pause` latency varies by more than 10x across microarchitectures (about
+/// 10 cycles before Skylake, about 140 on Skylake).
+#[cold]
+pub fn pause_cycles() -> u64 {
+    let start = now();
+    for _ in 0..PAUSE_SAMPLES {
+        core::hint::spin_loop();
+    }
+    (now().wrapping_sub(start) / PAUSE_SAMPLES).max(1)
+}
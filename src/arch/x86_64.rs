@@ -0,0 +1,67 @@
+use core::arch::asm;
+use core::arch::x86_64::{__cpuid_count, _rdtsc};
+use core::sync::atomic::{AtomicU8, AtomicU32, Ordering};
+
+/// TSC ticks a single `umwait` may sleep before re-polling.
+///
+/// Roughly a handful of `pause` latencies, so one iteration stays comparable
+/// to one iteration of the plain spin loop.
+const UMWAIT_TICKS: u64 = 256;
+
+/// `umwait` control: request the C0.1 state, which trades a little power for
+/// the fastest wakeup.
+const UMWAIT_C01: u32 = 1;
+
+const UNKNOWN: u8 = 0;
+const UNSUPPORTED: u8 = 1;
+const SUPPORTED: u8 = 2;
+
+static WAITPKG: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+/// Returns whether the CPU supports `umonitor`/`umwait` (CPUID.7.0:ECX[5]).
+#[inline]
+pub fn has_waitpkg() -> bool {
+    match WAITPKG.load(Ordering::Relaxed) {
+        UNKNOWN => {
+            #[allow(unused_unsafe)]
+            let leaf = unsafe { __cpuid_count(7, 0) };
+            let supported = leaf.ecx & (1 << 5) != 0;
+            let state = if supported { SUPPORTED } else { UNSUPPORTED };
+            WAITPKG.store(state, Ordering::Relaxed);
+            supported
+        }
+        state => state == SUPPORTED,
+    }
+}
+
+/// Busy phase built on `umonitor`/`umwait`.
+///
+/// The monitor is armed on the cache line of `wake` *before* polling `f`, so a
+/// notification landing between the poll and the `umwait` still ends the wait.
+///
+/// # Safety
+///
+/// The CPU must support `WAITPKG`, see [`has_waitpkg`].
+#[inline]
+pub unsafe fn busy_wait(f: &mut impl FnMut() -> bool, wake: &AtomicU32, iters: u32) -> bool {
+    let addr: *const AtomicU32 = wake;
+    for _ in 0..iters {
+        unsafe {
+            asm!("umonitor {}", in(reg) addr, options(nostack, preserves_flags));
+        }
+        if f() {
+            return true;
+        }
+        let deadline = unsafe { _rdtsc() } + UMWAIT_TICKS;
+        unsafe {
+            asm!(
+                "umwait {ctrl:e}",
+                ctrl = in(reg) UMWAIT_C01,
+                in("edx") (deadline >> 32) as u32,
+                in("eax") deadline as u32,
+                options(nostack),
+            );
+        }
+    }
+    false
+}
@@ -0,0 +1,64 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Condvar, Mutex, PoisonError};
+
+// std-only fallback for platforms without a native address-based wait.
+// Addresses hash into a fixed table of mutex/condvar buckets; the value is
+// checked under the bucket lock, and wakers take that lock after updating
+// it, so no wakeup is lost. Colliding addresses share a bucket, hence
+// `notify_all`.
+
+const BUCKETS: usize = 64;
+
+struct Bucket {
+    lock: Mutex<()>,
+    cond: Condvar,
+}
+
+static TABLE: [Bucket; BUCKETS] = [const {
+    Bucket {
+        lock: Mutex::new(()),
+        cond: Condvar::new(),
+    }
+}; BUCKETS];
+
+#[inline]
+fn bucket(ptr: *const AtomicU32) -> &'static Bucket {
+    // drop the alignment bits, then mix the rest into an index
+    let key = (ptr as usize >> 2).wrapping_mul(0x9e37_79b9);
+    &TABLE[(key >> 8) % BUCKETS]
+}
+
+#[inline]
+pub fn wait(a: &AtomicU32, expected: u32) {
+    let bucket = bucket(a);
+    let guard = bucket.lock.lock().unwrap_or_else(PoisonError::into_inner);
+    if a.load(Ordering::Relaxed) == expected {
+        drop(
+            bucket
+                .cond
+                .wait(guard)
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+    }
+}
+
+#[inline]
+pub fn wait_timeout(a: &AtomicU32, expected: u32, timeout: core::time::Duration) {
+    let bucket = bucket(a);
+    let guard = bucket.lock.lock().unwrap_or_else(PoisonError::into_inner);
+    if a.load(Ordering::Relaxed) == expected {
+        drop(
+            bucket
+                .cond
+                .wait_timeout(guard, timeout)
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+    }
+}
+
+#[inline]
+pub fn wake_one(ptr: *const AtomicU32) {
+    let bucket = bucket(ptr);
+    drop(bucket.lock.lock().unwrap_or_else(PoisonError::into_inner));
+    bucket.cond.notify_all();
+}
@@ -1,4 +1,5 @@
 use core::sync::atomic::AtomicU32;
+use core::time::Duration;
 
 #[inline]
 pub fn wait(a: &AtomicU32, expected: u32) {
@@ -14,6 +15,28 @@ pub fn wait(a: &AtomicU32, expected: u32) {
     };
 }
 
+#[inline]
+pub fn wait_timeout(a: &AtomicU32, expected: u32, timeout: Duration) {
+    let ptr: *const AtomicU32 = a;
+    let mut time = libc::_umtx_time {
+        _timeout: libc::timespec {
+            tv_sec: timeout.as_secs().min(libc::time_t::MAX as u64) as libc::time_t,
+            tv_nsec: timeout.subsec_nanos() as _,
+        },
+        _flags: 0, // relative
+        _clockid: libc::CLOCK_MONOTONIC as u32,
+    };
+    unsafe {
+        libc::_umtx_op(
+            ptr as *mut libc::c_void,
+            libc::UMTX_OP_WAIT_UINT_PRIVATE,
+            expected as libc::c_ulong,
+            core::mem::size_of::<libc::_umtx_time>() as *mut libc::c_void,
+            (&raw mut time).cast(),
+        );
+    };
+}
+
 #[inline]
 pub fn wake_one(ptr: *const AtomicU32) {
     unsafe {
@@ -39,3 +62,32 @@ pub fn wake_all(ptr: *const AtomicU32) {
         );
     };
 }
+
+#[cfg(feature = "ipc")]
+#[inline]
+pub fn wait_shared(a: &AtomicU32, expected: u32) {
+    let ptr: *const AtomicU32 = a;
+    unsafe {
+        libc::_umtx_op(
+            ptr as *mut libc::c_void,
+            libc::UMTX_OP_WAIT_UINT,
+            expected as libc::c_ulong,
+            core::ptr::null_mut(),
+            core::ptr::null_mut(),
+        );
+    };
+}
+
+#[cfg(feature = "ipc")]
+#[inline]
+pub fn wake_one_shared(ptr: *const AtomicU32) {
+    unsafe {
+        libc::_umtx_op(
+            ptr as *mut libc::c_void,
+            libc::UMTX_OP_WAKE,
+            1 as libc::c_ulong,
+            core::ptr::null_mut(),
+            core::ptr::null_mut(),
+        );
+    };
+}
@@ -1,4 +1,5 @@
 use core::sync::atomic::AtomicU32;
+use core::time::Duration;
 
 #[inline]
 pub fn wait(a: &AtomicU32, expected: u32) {
@@ -13,6 +14,23 @@ pub fn wait(a: &AtomicU32, expected: u32) {
     };
 }
 
+#[inline]
+pub fn wait_timeout(a: &AtomicU32, expected: u32, timeout: Duration) {
+    let ts = libc::timespec {
+        tv_sec: timeout.as_secs().min(libc::time_t::MAX as u64) as libc::time_t,
+        tv_nsec: timeout.subsec_nanos() as _,
+    };
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            a,
+            libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG,
+            expected,
+            &ts,
+        );
+    };
+}
+
 #[inline]
 pub fn wake_one(ptr: *const AtomicU32) {
     unsafe {
@@ -24,3 +42,25 @@ pub fn wake_one(ptr: *const AtomicU32) {
         );
     };
 }
+
+#[cfg(feature = "ipc")]
+#[inline]
+pub fn wait_shared(a: &AtomicU32, expected: u32) {
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            a,
+            libc::FUTEX_WAIT,
+            expected,
+            core::ptr::null::<libc::timespec>(),
+        );
+    };
+}
+
+#[cfg(feature = "ipc")]
+#[inline]
+pub fn wake_one_shared(ptr: *const AtomicU32) {
+    unsafe {
+        libc::syscall(libc::SYS_futex, ptr, libc::FUTEX_WAKE, 1i32);
+    };
+}
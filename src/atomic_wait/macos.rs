@@ -1,10 +1,13 @@
 use core::{
-    ffi::c_void,
+    ffi::{CStr, c_int, c_void},
     sync::atomic::{AtomicU32, Ordering::Relaxed},
+    time::Duration,
 };
+use std::sync::OnceLock;
 
 // On macOS, atomic wait/wake functionality is not available through
-// any public/stable C interface, but is available through libc++.
+// any public/stable C interface before macOS 14.4, but is available through
+// libc++.
 //
 // The libc++ functions declared below are are not publicly documented,
 // but they are part of the stable ABI.
@@ -17,7 +20,7 @@ use core::{
 // These exist since macOS 11, iOS 14, and watchOS 7.
 
 #[link(name = "c++")]
-extern "C" {
+unsafe extern "C" {
     // std::__1::__libcpp_atomic_monitor(void const volatile*)
     #[link_name = "_ZNSt3__123__libcpp_atomic_monitorEPVKv"]
     fn __libcpp_atomic_monitor(ptr: *const c_void) -> i64;
@@ -44,27 +47,166 @@ extern "C" {
     // See https://reviews.llvm.org/D114119#3193088
 }
 
+// libc++ waits on a table entry rather than on the atomic itself, and has no
+// timed wait. So where the kernel's address wait is reachable, every
+// operation goes through it instead, letting a timed wait be interrupted by
+// a wake: the public `os_sync_*` API (macOS 14.4+, iOS 17.4+), or else the
+// `__ulock_*` syscalls behind it (since macOS 10.12). Both are looked up at
+// runtime, so binaries still load on systems that lack them.
+
+// <os/os_sync_wait_on_address.h>
+type OsSyncWait = unsafe extern "C" fn(*mut c_void, u64, usize, u32) -> c_int;
+type OsSyncWaitTimeout = unsafe extern "C" fn(*mut c_void, u64, usize, u32, u32, u64) -> c_int;
+type OsSyncWake = unsafe extern "C" fn(*mut c_void, usize, u32) -> c_int;
+
+const OS_SYNC_FLAGS_NONE: u32 = 0;
+/// `OS_CLOCK_MACH_ABSOLUTE_TIME` from <os/clock.h>.
+const OS_CLOCK_MACH_ABSOLUTE_TIME: u32 = 32;
+
+// <sys/ulock.h> (XNU)
+type UlockWait = unsafe extern "C" fn(u32, *mut c_void, u64, u32) -> c_int;
+type UlockWake = unsafe extern "C" fn(u32, *mut c_void, u64) -> c_int;
+
+const UL_COMPARE_AND_WAIT: u32 = 1;
+const ULF_NO_ERRNO: u32 = 0x0100_0000;
+
+enum Backend {
+    OsSync {
+        wait: OsSyncWait,
+        wait_timeout: OsSyncWaitTimeout,
+        wake_any: OsSyncWake,
+    },
+    Ulock {
+        wait: UlockWait,
+        wake: UlockWake,
+    },
+    Libcxx,
+}
+
+static BACKEND: OnceLock<Backend> = OnceLock::new();
+
+#[inline(always)]
+fn backend() -> &'static Backend {
+    BACKEND.get_or_init(resolve)
+}
+
+#[cold]
+fn resolve() -> Backend {
+    // SAFETY: each symbol is looked up as the type it is declared with in
+    // the headers named above.
+    unsafe {
+        if let (Some(wait), Some(wait_timeout), Some(wake_any)) = (
+            lookup(c"os_sync_wait_on_address"),
+            lookup(c"os_sync_wait_on_address_with_timeout"),
+            lookup(c"os_sync_wake_by_address_any"),
+        ) {
+            return Backend::OsSync {
+                wait,
+                wait_timeout,
+                wake_any,
+            };
+        }
+        if let (Some(wait), Some(wake)) = (lookup(c"__ulock_wait"), lookup(c"__ulock_wake")) {
+            return Backend::Ulock { wait, wake };
+        }
+    }
+    Backend::Libcxx
+}
+
+/// Looks up `name` in the loaded images.
+///
+/// # Safety
+///
+/// `F` must be a function pointer type matching the symbol's signature.
+unsafe fn lookup<F: Copy>(name: &CStr) -> Option<F> {
+    let ptr = unsafe { libc::dlsym(libc::RTLD_DEFAULT, name.as_ptr()) };
+    (!ptr.is_null()).then(|| unsafe { core::mem::transmute_copy::<*mut c_void, F>(&ptr) })
+}
+
 #[inline]
 pub fn wait(a: &AtomicU32, expected: u32) {
     let ptr: *const AtomicU32 = a;
-    // The 'monitor' is just the notification counter associated
-    // with the address of the atomic.
-    let monitor = unsafe { __libcpp_atomic_monitor(ptr.cast()) };
-    // Check again if we should still go to sleep.
-    if a.load(Relaxed) != expected {
-        return;
+    let addr = ptr.cast_mut().cast();
+    match *backend() {
+        Backend::OsSync { wait, .. } => unsafe {
+            wait(addr, u64::from(expected), 4, OS_SYNC_FLAGS_NONE);
+        },
+        Backend::Ulock { wait, .. } => unsafe {
+            // a timeout of 0 waits forever
+            wait(
+                UL_COMPARE_AND_WAIT | ULF_NO_ERRNO,
+                addr,
+                u64::from(expected),
+                0,
+            );
+        },
+        Backend::Libcxx => {
+            // The 'monitor' is just the notification counter associated
+            // with the address of the atomic.
+            let monitor = unsafe { __libcpp_atomic_monitor(ptr.cast()) };
+            // Check again if we should still go to sleep.
+            if a.load(Relaxed) != expected {
+                return;
+            }
+            // Wait, but only if there's been no new notifications
+            // since we acquired the monitor.
+            unsafe { __libcpp_atomic_wait(ptr.cast(), monitor) };
+        }
     }
-    // Wait, but only if there's been no new notifications
-    // since we acquired the monitor.
-    unsafe { __libcpp_atomic_wait(ptr.cast(), monitor) };
 }
 
+/// Longest sleep of one [`wait_timeout`] call without a kernel address wait.
+const TIMEOUT_SLICE: Duration = Duration::from_millis(1);
+
 #[inline]
-pub fn wake_one(ptr: *const AtomicU32) {
-    unsafe { __cxx_atomic_notify_one(ptr.cast()) };
+pub fn wait_timeout(a: &AtomicU32, expected: u32, timeout: Duration) {
+    let ptr: *const AtomicU32 = a;
+    let addr = ptr.cast_mut().cast();
+    match *backend() {
+        Backend::OsSync { wait_timeout, .. } => unsafe {
+            let ns = timeout.as_nanos().clamp(1, u128::from(u64::MAX)) as u64;
+            wait_timeout(
+                addr,
+                u64::from(expected),
+                4,
+                OS_SYNC_FLAGS_NONE,
+                OS_CLOCK_MACH_ABSOLUTE_TIME,
+                ns,
+            );
+        },
+        Backend::Ulock { wait, .. } => unsafe {
+            // round up, and stay above 0, which means no timeout
+            let us = timeout
+                .as_nanos()
+                .div_ceil(1_000)
+                .clamp(1, u128::from(u32::MAX)) as u32;
+            wait(
+                UL_COMPARE_AND_WAIT | ULF_NO_ERRNO,
+                addr,
+                u64::from(expected),
+                us,
+            );
+        },
+        // libc++ has no timed wait, so sleep in short slices instead and let
+        // the caller re-check.
+        Backend::Libcxx => {
+            if a.load(Relaxed) == expected {
+                std::thread::sleep(timeout.min(TIMEOUT_SLICE));
+            }
+        }
+    }
 }
 
 #[inline]
-pub fn wake_all(ptr: *const AtomicU32) {
-    unsafe { __cxx_atomic_notify_all(ptr.cast()) };
+pub fn wake_one(ptr: *const AtomicU32) {
+    let addr = ptr.cast_mut().cast();
+    match *backend() {
+        Backend::OsSync { wake_any, .. } => unsafe {
+            wake_any(addr, 4, OS_SYNC_FLAGS_NONE);
+        },
+        Backend::Ulock { wake, .. } => unsafe {
+            wake(UL_COMPARE_AND_WAIT | ULF_NO_ERRNO, addr, 0);
+        },
+        Backend::Libcxx => unsafe { __cxx_atomic_notify_one(ptr.cast()) },
+    }
 }
@@ -7,8 +7,8 @@
 //!   (Linux currently only supports 32-bit futexes.)
 //! - Only the "wait", "wake one", and "wake all" operations are supported.
 //!   (Linux supports more operations, but Windows and macOS don't.)
-//! - No timeouts.
-//!   (macOS doesn't have a stable/public API for timeouts.)
+//! - Timeouts are relative, and on macOS only approximated by sleeping when
+//!   neither `os_sync_wait_on_address` nor `__ulock_wait` is available.
 //! - The wake operations don't return the number of threads woken up.
 //!   (Only Linux supports this.)
 //!
@@ -16,7 +16,12 @@
 //!    Linux 2.6.22+,
 //!    FreeBSD 11+,
 //!    Windows 8+, Windows Server 2012+,
-//!    macOS 11+, iOS 14+, watchOS 7+.
+//!    macOS 11+, iOS 14+, watchOS 7+,
+//!    wasm32 with the `atomics` target feature.
+//!
+//! Other platforms fall back to `parking_lot_core`'s address-keyed parking,
+//! or to a table of `std` mutex/condvar buckets without the `parking_lot`
+//! feature.
 //!
 //! ## Usage
 //!
@@ -38,15 +43,27 @@
 //!
 //! On Windows, this uses the `WaitOnAddress` and `WakeByAddress` APIs.
 //!
-//! On macOS (and iOS and watchOS), this uses `libc++`, making use of the same
+//! On macOS (and iOS and watchOS), this uses `os_sync_wait_on_address` and
+//! `os_sync_wake_by_address_any` where available (macOS 14.4+), or else the
+//! `__ulock_wait`/`__ulock_wake` syscalls behind them, both looked up at
+//! runtime. Without either, it falls back to `libc++`, making use of the same
 //! (ABI-stable) functions behind C++20's `atomic_wait` and `atomic_notify` functions.
 //!
+//! On wasm32, this uses the `memory.atomic.wait32` and `memory.atomic.notify`
+//! instructions, which require a shared memory (`-C target-feature=+atomics`).
+//!
+//! Elsewhere, this parks in `parking_lot_core`'s global table keyed on the
+//! atomic's address, so wakeups stay private to that address. Without the
+//! `parking_lot` feature it blocks on a `std` `Condvar` picked by hashing the
+//! address instead.
+//!
 //! ----------------------------------------------------------------------------------
 //!
 //! Vendored from <https://github.com/m-ou-se/atomic-wait>
 //! License: BSD-2-Clause (see LICENSES/LICENSE-atomic-wait)
 
 use core::sync::atomic::AtomicU32;
+use core::time::Duration;
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 #[path = "linux.rs"]
@@ -64,6 +81,24 @@ mod platform;
 #[path = "freebsd.rs"]
 mod platform;
 
+#[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+#[path = "wasm32.rs"]
+mod platform;
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "watchos",
+    windows,
+    target_os = "freebsd",
+    all(target_arch = "wasm32", target_feature = "atomics"),
+)))]
+#[cfg_attr(feature = "parking_lot", path = "parking_lot.rs")]
+#[cfg_attr(not(feature = "parking_lot"), path = "condvar.rs")]
+mod platform;
+
 /// If the value is `value`, wait until woken up.
 ///
 /// This function might also return spuriously,
@@ -73,6 +108,14 @@ pub fn wait(atomic: &AtomicU32, value: u32) {
     platform::wait(atomic, value)
 }
 
+/// Like [`wait`], but returns once `timeout` has elapsed.
+///
+/// This function might also return spuriously, or early.
+#[inline]
+pub fn wait_timeout(atomic: &AtomicU32, value: u32, timeout: Duration) {
+    platform::wait_timeout(atomic, value, timeout)
+}
+
 /// Wake one thread that is waiting on this atomic.
 ///
 /// It's okay if the pointer dangles or is null.
@@ -80,3 +123,25 @@ pub fn wait(atomic: &AtomicU32, value: u32) {
 pub fn wake_one(atomic: *const AtomicU32) {
     platform::wake_one(atomic);
 }
+
+/// Like [`wait`], but also woken by [`wake_one_shared`] from other processes
+/// mapping the same memory.
+#[cfg(all(
+    feature = "ipc",
+    any(target_os = "linux", target_os = "android", target_os = "freebsd")
+))]
+#[inline]
+pub fn wait_shared(atomic: &AtomicU32, value: u32) {
+    platform::wait_shared(atomic, value)
+}
+
+/// Wake one thread, in any process, that is waiting on this atomic with
+/// [`wait_shared`].
+#[cfg(all(
+    feature = "ipc",
+    any(target_os = "linux", target_os = "android", target_os = "freebsd")
+))]
+#[inline]
+pub fn wake_one_shared(atomic: *const AtomicU32) {
+    platform::wake_one_shared(atomic);
+}
@@ -0,0 +1,47 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+use parking_lot_core::{DEFAULT_PARK_TOKEN, DEFAULT_UNPARK_TOKEN};
+
+// Fallback for platforms without a native address-based wait. Threads park
+// in parking_lot_core's global table keyed on the atomic's address, so only
+// a wake on that same address can unpark them; unrelated `Thread::unpark`
+// calls from other libraries do not interfere.
+
+#[inline]
+pub fn wait(a: &AtomicU32, expected: u32) {
+    let key = a as *const AtomicU32 as usize;
+    // SAFETY: the validate and timeout callbacks don't call into
+    // parking_lot_core, and `before_sleep` does nothing.
+    unsafe {
+        parking_lot_core::park(
+            key,
+            || a.load(Ordering::Relaxed) == expected,
+            || {},
+            |_, _| {},
+            DEFAULT_PARK_TOKEN,
+            None,
+        );
+    }
+}
+
+#[inline]
+pub fn wait_timeout(a: &AtomicU32, expected: u32, timeout: core::time::Duration) {
+    let key = a as *const AtomicU32 as usize;
+    let deadline = std::time::Instant::now().checked_add(timeout);
+    // SAFETY: as in `wait`.
+    unsafe {
+        parking_lot_core::park(
+            key,
+            || a.load(Ordering::Relaxed) == expected,
+            || {},
+            |_, _| {},
+            DEFAULT_PARK_TOKEN,
+            deadline,
+        );
+    }
+}
+
+#[inline]
+pub fn wake_one(ptr: *const AtomicU32) {
+    // SAFETY: the callback doesn't call into parking_lot_core.
+    unsafe { parking_lot_core::unpark_one(ptr as usize, |_| DEFAULT_UNPARK_TOKEN) };
+}
@@ -0,0 +1,23 @@
+use core::arch::wasm32::{memory_atomic_notify, memory_atomic_wait32};
+use core::sync::atomic::AtomicU32;
+use core::time::Duration;
+
+#[inline]
+pub fn wait(a: &AtomicU32, expected: u32) {
+    let ptr: *const AtomicU32 = a;
+    // A negative timeout waits forever. Traps on threads that are not allowed
+    // to block, such as the browser's main thread.
+    unsafe { memory_atomic_wait32(ptr as *mut i32, expected as i32, -1) };
+}
+
+#[inline]
+pub fn wait_timeout(a: &AtomicU32, expected: u32, timeout: Duration) {
+    let ptr: *const AtomicU32 = a;
+    let ns = timeout.as_nanos().min(i64::MAX as u128) as i64;
+    unsafe { memory_atomic_wait32(ptr as *mut i32, expected as i32, ns) };
+}
+
+#[inline]
+pub fn wake_one(ptr: *const AtomicU32) {
+    unsafe { memory_atomic_notify(ptr as *mut i32, 1) };
+}
@@ -1,4 +1,5 @@
 use core::sync::atomic::AtomicU32;
+use core::time::Duration;
 use windows_sys::Win32::System::Threading::{
     INFINITE, WaitOnAddress, WakeByAddressAll, WakeByAddressSingle,
 };
@@ -10,6 +11,18 @@ pub fn wait(a: &AtomicU32, expected: u32) {
     unsafe { WaitOnAddress(ptr.cast(), expected_ptr.cast(), 4, INFINITE) };
 }
 
+#[inline]
+pub fn wait_timeout(a: &AtomicU32, expected: u32, timeout: Duration) {
+    let ptr: *const AtomicU32 = a;
+    let expected_ptr: *const u32 = &expected;
+    // round up, and stay below `INFINITE`
+    let ms = timeout
+        .as_nanos()
+        .div_ceil(1_000_000)
+        .min(u128::from(INFINITE - 1)) as u32;
+    unsafe { WaitOnAddress(ptr.cast(), expected_ptr.cast(), 4, ms) };
+}
+
 #[inline]
 pub fn wake_one(ptr: *const AtomicU32) {
     unsafe { WakeByAddressSingle(ptr.cast()) };
@@ -0,0 +1,126 @@
+//! Machine-specific [`Tuning`] recommendations.
+
+use crate::util::Tuning;
+use core::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+/// Round trips measured by [`calibrate`].
+const ROUNDS: u32 = 1_000;
+
+/// Polls after which a stalled side yields, in case the other thread got
+/// descheduled.
+const STALL_POLLS: u32 = 1 << 16;
+
+/// How many one-way handoffs the recommended busy phase covers.
+const HANDOFFS: u32 = 32;
+
+/// Recommends a [`Tuning`] for the current machine.
+///
+/// Runs a short ping-pong between two spinning threads (about a thousand
+/// round trips, typically well under a millisecond) to measure how many
+/// busy-phase iterations a cross-core handoff costs, and sizes the busy
+/// phase to cover a few dozen of them. On a single available CPU spinning
+/// can't observe a signal from another thread, so it recommends parking
+/// almost immediately instead.
+///
+/// Meant to be called once at startup; the result can be passed to
+/// [`Waiter::wait_with`](crate::Waiter::wait_with).
+///
+/// # Example
+///
+/// ```
+/// let tuning = waitx::calibrate();
+/// let (waker, waiter) = waitx::pair();
+/// waker.signal();
+/// waiter.wait_with(tuning);
+/// ```
+pub fn calibrate() -> Tuning {
+    let cpus = thread::available_parallelism().map_or(1, |n| n.get());
+    if cpus < 2 {
+        return Tuning::new(16, Tuning::DEFAULT.yield_iters);
+    }
+
+    let turn = Arc::new(AtomicU32::new(0));
+    let pong = {
+        let turn = turn.clone();
+        thread::spawn(move || {
+            for i in 0..ROUNDS {
+                spin_until(&turn, 2 * i + 1);
+                turn.store(2 * i + 2, Ordering::Release);
+            }
+        })
+    };
+
+    let mut samples = Vec::with_capacity(ROUNDS as usize);
+    for i in 0..ROUNDS {
+        turn.store(2 * i + 1, Ordering::Release);
+        samples.push(spin_until(&turn, 2 * i + 2));
+    }
+    let _ = pong.join();
+
+    samples.sort_unstable();
+    let round_trip = samples[samples.len() / 2];
+    let busy = (round_trip / 2).max(1).saturating_mul(HANDOFFS);
+    Tuning::new(busy.clamp(256, 1 << 16), Tuning::DEFAULT.yield_iters)
+}
+
+/// Spins like the busy phase until `turn` reaches `value`, returning the
+/// number of polls it took.
+fn spin_until(turn: &AtomicU32, value: u32) -> u32 {
+    let mut polls = 0u32;
+    while turn.load(Ordering::Acquire) != value {
+        polls = polls.wrapping_add(1);
+        if polls.is_multiple_of(STALL_POLLS) {
+            thread::yield_now();
+        } else {
+            core::hint::spin_loop();
+        }
+    }
+    polls
+}
+
+/// Returns whether logical CPUs `a` and `b` share a physical core, i.e. are
+/// the same CPU or SMT (hyperthread) siblings.
+///
+/// Reads the CPU topology from sysfs on Linux/Android; returns `None` where
+/// it is unavailable.
+pub fn shares_core(a: usize, b: usize) -> Option<bool> {
+    if a == b {
+        return Some(true);
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        let id = |cpu: usize, what: &str| {
+            std::fs::read_to_string(format!("/sys/devices/system/cpu/cpu{cpu}/topology/{what}"))
+                .ok()
+                .and_then(|s| s.trim().parse::<u32>().ok())
+        };
+        let core = |cpu| Some((id(cpu, "physical_package_id")?, id(cpu, "core_id")?));
+        Some(core(a)? == core(b)?)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    None
+}
+
+/// Factor the busy phase is shortened by when waiter and waker share a core.
+const SHARED_CORE_DIVISOR: u32 = 8;
+
+impl Tuning {
+    /// Adjusts the tuning for a waiter pinned to `waiter_cpu` and a waker
+    /// pinned to `waker_cpu`.
+    ///
+    /// If they share a physical core (see [`shares_core`]), a spinning
+    /// waiter competes with the waker for the same pipeline, so the busy
+    /// phase is cut eightfold and backs off exponentially. Otherwise, or if
+    /// the topology is unknown, the tuning is returned unchanged.
+    pub fn for_cpus(self, waiter_cpu: usize, waker_cpu: usize) -> Self {
+        if shares_core(waiter_cpu, waker_cpu) != Some(true) {
+            return self;
+        }
+        self.busy_iters((self.busy_iters / SHARED_CORE_DIVISOR).max(16))
+            .spin_strategy(crate::util::SpinStrategy::Backoff)
+    }
+}
@@ -16,9 +16,29 @@
 //! assert_eq!(rx.recv(), 42);
 //! ```
 
+use crate::fallible::{AllocError, TryArc};
 use crate::prelude::*;
 
-struct Slot<T> {
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+/// Storage for at most one value, handed back and forth between two sides.
+///
+/// The slot itself doesn't synchronize: whoever owns it at a given time is
+/// decided by the [`Event`]s around it. The writer may only call
+/// [`write`](Self::write)/[`uninit_mut`](Self::uninit_mut) while it owns the
+/// empty slot, then publishes it with [`mark_full`](Self::mark_full) and an
+/// event signal (a release); the reader may only [`read`](Self::read),
+/// [`get`](Self::get) or [`drop_value`](Self::drop_value) after consuming
+/// that signal (an acquire), and hands the slot back with
+/// [`mark_empty`](Self::mark_empty) and a signal of its own. The `full` flag
+/// only records whether a value is left for [`Drop`] to clean up.
+///
+/// All access goes through the pointer from [`UnsafeCell::get`], and no
+/// reference into the slot outlives the side's ownership of it.
+pub(crate) struct Slot<T> {
     inner: UnsafeCell<MaybeUninit<T>>,
     full: AtomicBool,
 }
@@ -38,6 +58,60 @@ impl<T> Slot<T> {
     pub fn is_full(&self) -> bool {
         self.full.load(Ordering::Acquire)
     }
+
+    /// Moves `value` into the slot.
+    ///
+    /// # Safety
+    ///
+    /// The caller must own the slot, and it must be empty.
+    #[inline(always)]
+    pub unsafe fn write(&self, value: T) {
+        unsafe { self.inner.get().cast::<T>().write(value) }
+    }
+
+    /// Returns the slot's storage for initializing in place.
+    ///
+    /// # Safety
+    ///
+    /// As for [`write`](Self::write); the reference must be dropped before
+    /// the slot is published.
+    #[allow(clippy::mut_from_ref)]
+    #[inline(always)]
+    pub unsafe fn uninit_mut(&self) -> &mut MaybeUninit<T> {
+        unsafe { &mut *self.inner.get() }
+    }
+
+    /// Moves the value out of the slot.
+    ///
+    /// # Safety
+    ///
+    /// The caller must own the slot, and it must be full. It is empty
+    /// afterwards.
+    #[inline(always)]
+    pub unsafe fn read(&self) -> T {
+        unsafe { self.inner.get().cast::<T>().read() }
+    }
+
+    /// Returns the value in the slot.
+    ///
+    /// # Safety
+    ///
+    /// The caller must own the slot, it must be full, and it must stay so
+    /// while the reference lives.
+    #[inline(always)]
+    pub unsafe fn get(&self) -> &T {
+        unsafe { &*self.inner.get().cast::<T>() }
+    }
+
+    /// Drops the value in the slot, in place.
+    ///
+    /// # Safety
+    ///
+    /// As for [`read`](Self::read).
+    #[inline(always)]
+    pub unsafe fn drop_value(&self) {
+        unsafe { core::ptr::drop_in_place(self.inner.get().cast::<T>()) }
+    }
 }
 
 impl<T> Default for Slot<T> {
@@ -52,10 +126,8 @@ impl<T> Default for Slot<T> {
 impl<T> Drop for Slot<T> {
     fn drop(&mut self) {
         if self.is_full() {
-            // SAFETY: the value has to exist at this point.
-            unsafe {
-                (*self.inner.get()).assume_init_drop();
-            }
+            // SAFETY: `&mut self` owns the slot, and it is full.
+            unsafe { self.drop_value() }
         }
     }
 }
@@ -63,102 +135,1403 @@ impl<T> Drop for Slot<T> {
 unsafe impl<T: Send> Send for Slot<T> {}
 unsafe impl<T: Send> Sync for Slot<T> {}
 
-struct Inner<T> {
-    slot: Arc<Slot<T>>,
-    tx: Waker,
-    rx: Waiter,
+/// Error returned by a receive on a channel that was closed, with
+/// [`Sender::close`] or [`Receiver::close`] or by dropping either side, and
+/// has no value left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Closed(pub(crate) ());
+
+impl core::fmt::Display for Closed {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("channel closed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Closed {}
+
+/// The state both halves of a channel share.
+///
+/// Channels normally keep it in an `Arc`; create one with
+/// [`ChannelState::new`] to store it elsewhere, see [`channel_in`].
+pub struct ChannelState<T> {
+    slot: Slot<T>,
+    /// Signaled by the sender once the slot is full.
+    full: Event,
+    /// Signaled by the receiver once the slot is empty.
+    empty: Event,
+    /// The threads inside a send and a receive, tracked in debug builds to
+    /// catch misuse.
+    sending: ThreadTag,
+    receiving: ThreadTag,
+    /// Each side's endpoint and weak handles, see [`Sender::downgrade`].
+    senders: Handles,
+    receivers: Handles,
+    /// Set once either side closed the channel.
+    closed: AtomicBool,
+}
+
+impl<T> ChannelState<T> {
+    /// Creates the state of a new, empty channel.
+    pub fn new() -> Self {
+        Self {
+            slot: Slot::default(),
+            full: Event::new(),
+            // the slot starts empty, so the first send goes through
+            empty: Event::with_initial(1),
+            sending: ThreadTag::new(),
+            receiving: ThreadTag::new(),
+            senders: Handles::new(),
+            receivers: Handles::new(),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the channel to its initial, empty state, so it can be reused
+    /// for another round of [`channel_in`] without reallocating.
+    ///
+    /// A value left in the slot is dropped. Taking `&mut self` guarantees
+    /// that no endpoint still uses it, so no thread can be waiting.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Marks the channel closed and wakes both sides, without signaling
+    /// either event, so only waits that check [`is_closed`](Self::is_closed)
+    /// return.
+    fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.full.signal_n(0);
+        self.empty.signal_n(0);
+    }
+
+    #[inline(always)]
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+}
+
+impl<T> Default for ChannelState<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One side's handles: its endpoint, and the weak handles that can take its
+/// place once it is dropped.
+struct Handles {
+    /// Set while the side's endpoint exists.
+    alive: AtomicBool,
+    /// How many weak handles of the side exist.
+    weak: AtomicU32,
+    /// Notifications the endpoint consumed, saved when it is dropped for the
+    /// weak handle taking over.
+    consumed: AtomicCount,
+}
+
+impl Handles {
+    fn new() -> Self {
+        Self {
+            alive: AtomicBool::new(true),
+            weak: AtomicU32::new(0),
+            consumed: AtomicCount::new(0),
+        }
+    }
+
+    /// Marks the endpoint dropped, having consumed `consumed` notifications;
+    /// returns whether the side is gone, no weak handle being left to take
+    /// over.
+    fn release(&self, consumed: Count) -> bool {
+        self.consumed.store(consumed, Ordering::Relaxed);
+        self.alive.store(false, Ordering::SeqCst);
+        self.weak.load(Ordering::SeqCst) == 0
+    }
+
+    #[cfg(not(feature = "loom"))]
+    fn add_weak(&self) {
+        // as for `Arc::clone`, the new handle comes from an existing one
+        self.weak.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Forgets a weak handle; returns whether the side is gone, it being
+    /// the last one and the endpoint dropped.
+    #[cfg(not(feature = "loom"))]
+    fn drop_weak(&self) -> bool {
+        self.weak.fetch_sub(1, Ordering::SeqCst) == 1 && !self.alive.load(Ordering::SeqCst)
+    }
+
+    /// Takes the dropped endpoint's place, returning the notifications it
+    /// consumed, or `None` while the endpoint exists.
+    #[cfg(not(feature = "loom"))]
+    fn claim(&self) -> Option<Count> {
+        self.alive
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::Relaxed)
+            .ok()?;
+        Some(self.consumed.load(Ordering::Relaxed))
+    }
+}
+
+/// One half of the channel.
+struct Inner<S> {
+    shared: S,
+    state: WaitState,
 }
 
 /// Sending half of a single-slot synchronous channel.
-pub struct Sender<T>(Inner<T>);
+///
+/// `S` is how it holds the shared [`ChannelState`]: an `Arc` unless the
+/// channel was created with [`channel_in`].
+pub struct Sender<T, S: Deref<Target = ChannelState<T>> = Arc<ChannelState<T>>>(
+    Inner<S>,
+    PhantomData<fn(T)>,
+);
 
-impl<T> Sender<T> {
+impl<T, S: Deref<Target = ChannelState<T>>> Sender<T, S> {
     /// Sends a value, blocking indefinitely until the slot becomes empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel is closed, see
+    /// [`send_unless_closed`](Self::send_unless_closed).
     #[inline]
     pub fn send(&self, value: T) {
+        if self.send_unless_closed(value).is_err() {
+            panic!("waitx: send on a closed channel");
+        }
+    }
+
+    /// Sends a value, blocking until the slot becomes empty, or returns it if
+    /// the channel is or gets closed first.
+    #[inline]
+    pub fn send_unless_closed(&self, value: T) -> Result<(), T> {
+        let _use = self.0.shared.sending.enter("Sender");
+        self.check_self_wait();
+        let shared = &*self.0.shared;
+
         // wait until the slot is empty
-        self.0.rx.wait();
+        if !self.acquire(self.0.state.tuning()) {
+            return Err(value);
+        }
 
         // write the value
+        // SAFETY: consuming `empty` handed us the slot, and it is empty.
         unsafe {
-            (*self.0.slot.inner.get()).write(value);
+            shared.slot.write(value);
         }
 
         // mark slot as full
-        self.0.slot.mark_full();
+        shared.slot.mark_full();
 
         // notify receiver
-        self.0.tx.signal();
+        shared.full.signal();
+        Ok(())
+    }
+
+    /// Sends a value constructed directly in the channel slot, blocking until
+    /// the slot is empty.
+    ///
+    /// If `init` panics, the slot is handed back to the sender as still
+    /// empty.
+    ///
+    /// # Safety
+    ///
+    /// `init` must fully initialize the slot it is given.
+    #[inline]
+    pub unsafe fn send_with(&self, init: impl FnOnce(&mut MaybeUninit<T>)) {
+        let _use = self.0.shared.sending.enter("Sender");
+        let permit = self.reserve();
+        init(permit.slot());
+        permit.commit();
+    }
+
+    /// Blocks until the slot is empty and reserves it for one value.
+    ///
+    /// The value is only needed once the returned permit is used, so it can
+    /// be computed after the wait. Dropping the permit without sending gives
+    /// the slot back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel is closed.
+    #[inline]
+    pub fn reserve(&self) -> SendPermit<'_, T, S> {
+        let _use = self.0.shared.sending.enter("Sender");
+        self.check_self_wait();
+        if !self.acquire(self.0.state.tuning()) {
+            panic!("waitx: send on a closed channel");
+        }
+        SendPermit { tx: self }
     }
 
-    /// Attempts to send a value without blocking, returning it if the slot is full.
+    /// Reserves the slot if it is empty and the channel open, without
+    /// blocking.
+    #[inline]
+    pub fn try_reserve(&self) -> Option<SendPermit<'_, T, S>> {
+        let _use = self.0.shared.sending.enter("Sender");
+        if self.0.shared.is_closed() || !self.0.state.try_wait(&self.0.shared.empty) {
+            return None;
+        }
+        Some(SendPermit { tx: self })
+    }
+
+    /// Attempts to send a value without blocking, returning it if the slot is
+    /// full or the channel closed.
     #[inline(always)]
     pub fn try_send(&self, value: T) -> Result<(), T> {
+        let _use = self.0.shared.sending.enter("Sender");
+        let shared = &*self.0.shared;
+
         // exit early if already full
-        if !self.0.rx.try_wait() {
+        if shared.is_closed() || !self.0.state.try_wait(&shared.empty) {
             return Err(value);
         }
+        // SAFETY: as in `send`.
         unsafe {
-            (*self.0.slot.inner.get()).write(value);
+            shared.slot.write(value);
         }
-        self.0.slot.mark_full();
-        self.0.tx.signal();
+        shared.slot.mark_full();
+        shared.full.signal();
         Ok(())
     }
+
+    /// Closes the channel: the receiver gets the value still in the slot, if
+    /// any, and then [`Closed`], and further sends fail.
+    ///
+    /// Wakes the receiver if it is blocked. Dropping the sender does the
+    /// same; `close` ends the stream while the sender is kept around, e.g. in
+    /// a struct.
+    pub fn close(&self) {
+        self.0.shared.close();
+    }
+
+    /// Returns whether either side closed the channel.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.0.shared.is_closed()
+    }
+
+    /// Panics if a blocking send could only be woken by a receive on this
+    /// thread, which last used the receiver, instead of deadlocking. Debug
+    /// builds only.
+    #[inline(always)]
+    fn check_self_wait(&self) {
+        let shared = &*self.0.shared;
+        if self.is_full()
+            && shared
+                .receiving
+                .stuck_here(|| self.is_full() && !shared.is_closed())
+        {
+            panic!(
+                "waitx: send would block forever: the slot is full and this thread last used the receiver"
+            );
+        }
+    }
+
+    /// Waits for the empty slot; returns `false` if the channel is or gets
+    /// closed first.
+    #[inline(always)]
+    fn acquire(&self, tuning: Tuning) -> bool {
+        let shared = &*self.0.shared;
+        !shared.is_closed()
+            && self
+                .0
+                .state
+                .wait_unless(&shared.empty, tuning, || shared.is_closed())
+    }
+
+    /// Returns whether the receiver is blocked in a receive right now,
+    /// spinning or parked.
+    ///
+    /// A snapshot like [`Waker::waiter_is_parked`].
+    #[inline]
+    pub fn receiver_is_waiting(&self) -> bool {
+        self.0.shared.full.is_waiting()
+    }
+
+    /// Returns whether `rx` is the other half of this channel.
+    #[inline]
+    pub fn same_channel(&self, rx: &Receiver<T, S>) -> bool {
+        core::ptr::eq(&*self.0.shared, &*rx.0.shared)
+    }
+
+    /// Returns whether a send would block right now, i.e. the receiver hasn't
+    /// taken the last value yet.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        !self.0.state.ready(&self.0.shared.empty)
+    }
+
+    /// Returns whether a send would go through right now.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.is_full()
+    }
+
+    /// Returns how many values are in the channel, as seen by the sender.
+    #[inline]
+    pub fn len(&self) -> usize {
+        usize::from(self.is_full())
+    }
+
+    /// Returns how many values the channel holds at most: one.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        1
+    }
+
+    /// Takes the sender apart without closing the channel.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn take_inner(self) -> Inner<S> {
+        let this = core::mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again.
+        unsafe { core::ptr::read(&this.0) }
+    }
+}
+
+impl<T, S: Deref<Target = ChannelState<T>>> Drop for Sender<T, S> {
+    fn drop(&mut self) {
+        let shared = &*self.0.shared;
+        if shared.senders.release(self.0.state.consumed()) {
+            shared.close();
+        }
+    }
+}
+
+/// The empty slot, reserved with [`Sender::reserve`].
+pub struct SendPermit<'a, T, S: Deref<Target = ChannelState<T>> = Arc<ChannelState<T>>> {
+    tx: &'a Sender<T, S>,
+}
+
+impl<T, S: Deref<Target = ChannelState<T>>> SendPermit<'_, T, S> {
+    /// Sends `value` through the reserved slot, without blocking.
+    #[inline]
+    pub fn send(self, value: T) {
+        self.slot().write(value);
+        self.commit();
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    #[inline(always)]
+    fn slot(&self) -> &mut MaybeUninit<T> {
+        // SAFETY: the slot is empty and reserved by this permit; the receiver
+        // doesn't touch it until it is marked full.
+        unsafe { self.tx.0.shared.slot.uninit_mut() }
+    }
+
+    /// Publishes the value written to the slot.
+    #[inline(always)]
+    fn commit(self) {
+        let shared = &*self.tx.0.shared;
+        shared.slot.mark_full();
+        shared.full.signal();
+        core::mem::forget(self);
+    }
+}
+
+impl<T, S: Deref<Target = ChannelState<T>>> Drop for SendPermit<'_, T, S> {
+    fn drop(&mut self) {
+        self.tx.0.shared.empty.signal();
+    }
 }
 
 /// Receiving half of a single-slot synchronous channel.
-pub struct Receiver<T>(Inner<T>);
+///
+/// `S` is how it holds the shared [`ChannelState`], as for [`Sender`].
+pub struct Receiver<T, S: Deref<Target = ChannelState<T>> = Arc<ChannelState<T>>>(
+    Inner<S>,
+    PhantomData<fn() -> T>,
+);
 
-impl<T> Receiver<T> {
+impl<T, S: Deref<Target = ChannelState<T>>> Receiver<T, S> {
     /// Receives a value, blocking until one is available.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel is closed and empty, see
+    /// [`recv_unless_closed`](Self::recv_unless_closed).
     #[inline(always)]
     pub fn recv(&self) -> T {
-        self.0.rx.wait();
+        self.recv_unless_closed()
+            .unwrap_or_else(|_| panic!("waitx: recv on a closed channel"))
+    }
+
+    /// Receives a value, blocking until one is available, or returns
+    /// [`Closed`] once the channel is closed and empty.
+    #[inline(always)]
+    pub fn recv_unless_closed(&self) -> Result<T, Closed> {
+        let _use = self.0.shared.receiving.enter("Receiver");
+        self.check_self_wait();
+        if !self.acquire(self.0.state.tuning()) {
+            return Err(Closed(()));
+        }
+        Ok(self.get())
+    }
+
+    /// Receives a value, parking right away without spinning first.
+    ///
+    /// See [`Waiter::wait_park_only`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel is closed and empty.
+    #[inline]
+    pub fn recv_parking(&self) -> T {
+        let _use = self.0.shared.receiving.enter("Receiver");
+        self.check_self_wait();
+        if !self.acquire(Tuning::new(0, 0)) {
+            panic!("waitx: recv on a closed channel");
+        }
         self.get()
     }
 
+    /// Closes the channel: a value still in the slot can be received, and
+    /// then receives return [`Closed`]. A sender blocked in a send is woken
+    /// and its send fails; see [`Sender::close`].
+    pub fn close(&self) {
+        self.0.shared.close();
+    }
+
+    /// Returns whether either side closed the channel.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.0.shared.is_closed()
+    }
+
+    /// Waits for a value; returns `false` if the channel is or gets closed
+    /// while empty.
+    #[inline(always)]
+    fn acquire(&self, tuning: Tuning) -> bool {
+        let shared = &*self.0.shared;
+        self.0
+            .state
+            .wait_unless(&shared.full, tuning, || shared.is_closed())
+    }
+
+    /// Receives a value, blocking for at most `timeout`; returns `None` if
+    /// none arrived in time.
+    ///
+    /// See [`Waiter::wait_timeout`].
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    #[inline]
+    pub fn recv_timeout(&self, timeout: core::time::Duration) -> Option<T> {
+        let _use = self.0.shared.receiving.enter("Receiver");
+        if !self.0.state.wait_timeout(&self.0.shared.full, timeout) {
+            return None;
+        }
+        Some(self.get())
+    }
+
+    /// Sets the expected wake latency of blocking receives; see
+    /// [`Waiter::set_latency_sla`].
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    pub fn set_latency_sla(
+        &mut self,
+        limit: core::time::Duration,
+        on_breach: impl Fn(core::time::Duration) + Send + Sync + 'static,
+    ) {
+        self.0
+            .state
+            .set_latency_sla(&self.0.shared.full, limit, on_breach);
+    }
+
+    /// Returns how many receives exceeded the latency set with
+    /// [`set_latency_sla`](Self::set_latency_sla).
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    pub fn sla_breaches(&self) -> u64 {
+        self.0.state.sla_breaches()
+    }
+
     /// Attempts to receive a value without blocking.
     #[inline(always)]
     pub fn try_recv(&self) -> Option<T> {
-        if !self.0.rx.try_wait() {
+        let _use = self.0.shared.receiving.enter("Receiver");
+        if !self.0.state.try_wait(&self.0.shared.full) {
             return None;
         }
         Some(self.get())
     }
 
+    /// Consumes the receiver and returns the value left in the slot, if any,
+    /// instead of letting the channel drop it at teardown.
+    ///
+    /// A value the sender is still writing is not waited for.
+    pub fn into_inner(self) -> Option<T> {
+        self.try_recv()
+    }
+
+    /// Returns an iterator that receives values, blocking for each.
+    ///
+    /// The iterator ends once the channel is closed and empty, see
+    /// [`Sender::close`].
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T, S> {
+        Iter { rx: self }
+    }
+
+    /// Returns an iterator over the values available right now, without
+    /// blocking.
+    #[inline]
+    pub fn try_iter(&self) -> TryIter<'_, T, S> {
+        TryIter { rx: self }
+    }
+
+    /// Returns whether a receive would block right now.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.0.state.ready(&self.0.shared.full)
+    }
+
+    /// Returns whether a value is waiting to be received.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        !self.is_empty()
+    }
+
+    /// Returns how many values are waiting to be received.
+    #[inline]
+    pub fn len(&self) -> usize {
+        usize::from(self.is_full())
+    }
+
+    /// Returns how many values the channel holds at most: one.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        1
+    }
+
+    /// Returns whether `tx` is the other half of this channel.
+    #[inline]
+    pub fn same_channel(&self, tx: &Sender<T, S>) -> bool {
+        tx.same_channel(self)
+    }
+
+    /// Returns the value waiting in the slot, if any, without receiving it.
+    ///
+    /// The value stays in the channel and the sender isn't signaled; a later
+    /// receive returns it as usual.
+    #[inline]
+    pub fn peek(&mut self) -> Option<&T> {
+        if !self.0.state.ready(&self.0.shared.full) {
+            return None;
+        }
+        // SAFETY: the pending `full` notification hands us the full slot,
+        // and `&mut self` keeps it from being received while borrowed.
+        Some(unsafe { self.0.shared.slot.get() })
+    }
+
+    /// Receives up to `max` values into `buf`, blocking until at least one is
+    /// available, and returns how many were received.
+    ///
+    /// After the first value it only takes what is already available, so a
+    /// single blocking wait covers the whole batch. Returns 0 right away if
+    /// `max` is 0, or once the channel is closed and empty.
+    pub fn recv_many(&self, buf: &mut Vec<T>, max: usize) -> usize {
+        if max == 0 {
+            return 0;
+        }
+        let Ok(value) = self.recv_unless_closed() else {
+            return 0;
+        };
+        buf.push(value);
+        1 + self.drain_into(buf, max - 1)
+    }
+
+    /// Moves every value available right now into `buf`, without blocking,
+    /// and returns how many were received.
+    pub fn try_drain(&self, buf: &mut Vec<T>) -> usize {
+        self.drain_into(buf, usize::MAX)
+    }
+
+    #[inline]
+    fn drain_into(&self, buf: &mut Vec<T>, max: usize) -> usize {
+        let mut n = 0;
+        while n < max {
+            let Some(value) = self.try_recv() else {
+                break;
+            };
+            buf.push(value);
+            n += 1;
+        }
+        n
+    }
+
+    /// Receives a value, blocking until one is available, and lends it to
+    /// `f` in place, returning its result.
+    ///
+    /// As with [`recv_ref`](Self::recv_ref), the value is never moved out of
+    /// the slot: it is dropped and the slot handed back to the sender once
+    /// `f` returns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel is closed and empty.
+    #[inline]
+    pub fn recv_with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.recv_ref())
+    }
+
+    /// Receives a value, blocking until one is available, and lends it out
+    /// in place.
+    ///
+    /// The slot is only released to the sender once the returned guard is
+    /// dropped, which also drops the value, saving the move out of the slot
+    /// for large payloads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel is closed and empty.
+    #[inline]
+    pub fn recv_ref(&self) -> RecvGuard<'_, T, S> {
+        let _use = self.0.shared.receiving.enter("Receiver");
+        self.check_self_wait();
+        if !self.acquire(self.0.state.tuning()) {
+            panic!("waitx: recv on a closed channel");
+        }
+        RecvGuard { rx: self }
+    }
+
+    /// Panics if a blocking receive could only be woken by a send on this
+    /// thread, which last used the sender or is in the middle of a send,
+    /// e.g. from inside the `init` of [`Sender::send_with`], instead of
+    /// deadlocking. Debug builds only.
+    #[inline(always)]
+    fn check_self_wait(&self) {
+        let shared = &*self.0.shared;
+        if self.is_empty()
+            && shared
+                .sending
+                .stuck_here(|| self.is_empty() && !shared.is_closed())
+        {
+            panic!(
+                "waitx: recv would block forever: the slot is empty and this thread last used the sender"
+            );
+        }
+    }
+
     /// Reads and removes the current value from the slot.
     #[inline(always)]
     fn get(&self) -> T {
-        // SAFETY: slot must be full at this point.
-        let value = unsafe { (*self.0.slot.inner.get()).assume_init_read() };
+        // SAFETY: consuming `full` handed us the slot, and it is full.
+        let value = unsafe { self.0.shared.slot.read() };
+        self.release();
+        value
+    }
 
-        self.0.slot.mark_empty();
-        self.0.tx.signal();
+    /// Hands the emptied slot back to the sender.
+    #[inline(always)]
+    fn release(&self) {
+        let shared = &*self.0.shared;
+        shared.slot.mark_empty();
+        shared.empty.signal();
+    }
 
-        value
+    /// Takes the receiver apart without closing the channel.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn take_inner(self) -> Inner<S> {
+        let this = core::mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again.
+        unsafe { core::ptr::read(&this.0) }
+    }
+}
+
+impl<T, S: Deref<Target = ChannelState<T>>> Drop for Receiver<T, S> {
+    fn drop(&mut self) {
+        let shared = &*self.0.shared;
+        if shared.receivers.release(self.0.state.consumed()) {
+            shared.close();
+        }
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+impl<T> Sender<T> {
+    /// Returns a weak handle that doesn't keep the channel alive, e.g. to
+    /// keep it in a registry.
+    ///
+    /// A channel has one sender at a time, so the handle can only be
+    /// upgraded once this sender is dropped. Dropping the sender doesn't
+    /// close the channel while weak handles are left to take over; the last
+    /// of them dropped without upgrading closes it.
+    pub fn downgrade(&self) -> WeakSender<T> {
+        WeakSender(WeakInner::new(&self.0, |shared| &shared.senders))
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+impl<T> Receiver<T> {
+    /// Returns a weak handle that doesn't keep the channel alive; see
+    /// [`Sender::downgrade`].
+    pub fn downgrade(&self) -> WeakReceiver<T> {
+        WeakReceiver(WeakInner::new(&self.0, |shared| &shared.receivers))
+    }
+}
+
+/// One half of the channel, not keeping it alive.
+#[cfg(not(feature = "loom"))]
+struct WeakInner<T> {
+    shared: alloc::sync::Weak<ChannelState<T>>,
+    tuning: TuningSource,
+    /// The handles of the half this one stands in for.
+    side: fn(&ChannelState<T>) -> &Handles,
+}
+
+#[cfg(not(feature = "loom"))]
+impl<T> WeakInner<T> {
+    fn new(inner: &Inner<Arc<ChannelState<T>>>, side: fn(&ChannelState<T>) -> &Handles) -> Self {
+        side(&inner.shared).add_weak();
+        Self {
+            shared: Arc::downgrade(&inner.shared),
+            tuning: inner.state.tuning_source(),
+            side,
+        }
+    }
+
+    fn upgrade(&self) -> Option<Inner<Arc<ChannelState<T>>>> {
+        let shared = self.shared.upgrade()?;
+        if shared.is_closed() {
+            return None;
+        }
+        let consumed = (self.side)(&shared).claim()?;
+        Some(Inner {
+            shared,
+            state: WaitState::resume(self.tuning.clone(), consumed),
+        })
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+impl<T> Drop for WeakInner<T> {
+    fn drop(&mut self) {
+        // the last handle left of a dropped endpoint takes the half with it
+        if let Some(shared) = self.shared.upgrade()
+            && (self.side)(&shared).drop_weak()
+        {
+            shared.close();
+        }
+    }
+}
+
+/// A [`Sender`] that doesn't keep the channel alive, from
+/// [`Sender::downgrade`].
+#[cfg(not(feature = "loom"))]
+pub struct WeakSender<T>(WeakInner<T>);
+
+#[cfg(not(feature = "loom"))]
+impl<T> WeakSender<T> {
+    /// Returns a sender taking over from the dropped one, or `None` while
+    /// that one (or one upgraded before) is alive, or once the channel is
+    /// closed.
+    pub fn upgrade(&self) -> Option<Sender<T>> {
+        Some(Sender(self.0.upgrade()?, PhantomData))
+    }
+}
+
+/// A [`Receiver`] that doesn't keep the channel alive, from
+/// [`Receiver::downgrade`].
+#[cfg(not(feature = "loom"))]
+pub struct WeakReceiver<T>(WeakInner<T>);
+
+#[cfg(not(feature = "loom"))]
+impl<T> WeakReceiver<T> {
+    /// Returns a receiver taking over from the dropped one, or `None` while
+    /// that one (or one upgraded before) is alive, or once the channel is
+    /// closed.
+    pub fn upgrade(&self) -> Option<Receiver<T>> {
+        Some(Receiver(self.0.upgrade()?, PhantomData))
+    }
+}
+
+/// Blocking iterator over received values, see [`Receiver::iter`].
+pub struct Iter<'a, T, S: Deref<Target = ChannelState<T>> = Arc<ChannelState<T>>> {
+    rx: &'a Receiver<T, S>,
+}
+
+impl<T, S: Deref<Target = ChannelState<T>>> Iterator for Iter<'_, T, S> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv_unless_closed().ok()
+    }
+}
+
+/// Owning blocking iterator over received values; like [`Iter`], it ends
+/// once the channel is closed and empty.
+pub struct IntoIter<T, S: Deref<Target = ChannelState<T>> = Arc<ChannelState<T>>> {
+    rx: Receiver<T, S>,
+}
+
+impl<T, S: Deref<Target = ChannelState<T>>> Iterator for IntoIter<T, S> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv_unless_closed().ok()
+    }
+}
+
+impl<T, S: Deref<Target = ChannelState<T>>> IntoIterator for Receiver<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T, S>;
+
+    fn into_iter(self) -> IntoIter<T, S> {
+        IntoIter { rx: self }
+    }
+}
+
+impl<'a, T, S: Deref<Target = ChannelState<T>>> IntoIterator for &'a Receiver<T, S> {
+    type Item = T;
+    type IntoIter = Iter<'a, T, S>;
+
+    fn into_iter(self) -> Iter<'a, T, S> {
+        self.iter()
+    }
+}
+
+/// Sends every value of the iterator in order, blocking as [`Sender::send`]
+/// does.
+impl<T, S: Deref<Target = ChannelState<T>>> Extend<T> for Sender<T, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let _use = self.0.shared.sending.enter("Sender");
+        for value in iter {
+            self.send(value);
+        }
+    }
+}
+
+/// Non-blocking iterator over available values, see [`Receiver::try_iter`].
+pub struct TryIter<'a, T, S: Deref<Target = ChannelState<T>> = Arc<ChannelState<T>>> {
+    rx: &'a Receiver<T, S>,
+}
+
+impl<T, S: Deref<Target = ChannelState<T>>> Iterator for TryIter<'_, T, S> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.rx.try_recv()
+    }
+}
+
+/// A value received with [`Receiver::recv_ref`], still in the channel slot.
+///
+/// Dropping it drops the value and frees the slot for the next send.
+pub struct RecvGuard<'a, T, S: Deref<Target = ChannelState<T>> = Arc<ChannelState<T>>> {
+    rx: &'a Receiver<T, S>,
+}
+
+impl<T, S: Deref<Target = ChannelState<T>>> Deref for RecvGuard<'_, T, S> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // SAFETY: the slot stays full until the guard is dropped.
+        unsafe { self.rx.0.shared.slot.get() }
+    }
+}
+
+impl<T, S: Deref<Target = ChannelState<T>>> Drop for RecvGuard<'_, T, S> {
+    fn drop(&mut self) {
+        // SAFETY: the slot is full, and the sender doesn't touch it before
+        // `release`.
+        unsafe {
+            self.rx.0.shared.slot.drop_value();
+        }
+        self.rx.release();
     }
 }
 
 /// Creates a new single-slot synchronous channel.
+///
+/// Its blocking operations use the calling thread's tuning, see
+/// [`set_thread_tuning`](crate::set_thread_tuning).
 pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
-    let (tx_1, rx_1) = pair();
-    let (tx_2, rx_2) = pair();
-    let slot_tx = Arc::new(Slot::default());
-    let slot_rx = slot_tx.clone();
-
-    let inner_tx = Inner {
-        slot: slot_tx,
-        tx: tx_1,
-        rx: rx_2,
+    with_tuning(|| TuningSource::Thread)
+}
+
+/// Creates a new single-slot synchronous channel whose blocking
+/// [`Sender::send`] and [`Receiver::recv`] use `tuning`.
+pub fn channel_with_tuning<T>(tuning: Tuning) -> (Sender<T>, Receiver<T>) {
+    with_tuning(|| TuningSource::Fixed(tuning))
+}
+
+/// Creates a new single-slot synchronous channel whose blocking operations
+/// use the current value of `tuning`; see [`SharedTuning`].
+#[cfg(feature = "std")]
+pub fn channel_with_shared_tuning<T>(tuning: &SharedTuning) -> (Sender<T>, Receiver<T>) {
+    with_tuning(|| TuningSource::shared(tuning))
+}
+
+/// Configures channels before creating them, as the one place channel
+/// options hang off of.
+///
+/// ```
+/// let tuning = waitx::Tuning::new(1_000, 10);
+/// let (tx, rx) = waitx::Builder::new().tuning(tuning).build();
+///
+/// std::thread::spawn(move || tx.send(42));
+/// assert_eq!(rx.recv(), 42);
+/// ```
+#[derive(Clone)]
+pub struct Builder {
+    tuning: TuningSource,
+}
+
+impl Builder {
+    /// Starts from the defaults of [`channel`]: blocking operations use the
+    /// calling thread's tuning.
+    pub fn new() -> Self {
+        Self {
+            tuning: TuningSource::Thread,
+        }
+    }
+
+    /// Makes blocking operations use `tuning`, like [`channel_with_tuning`].
+    pub fn tuning(mut self, tuning: Tuning) -> Self {
+        self.tuning = TuningSource::Fixed(tuning);
+        self
+    }
+
+    /// Makes blocking operations use the current value of `tuning`, like
+    /// [`channel_with_shared_tuning`].
+    #[cfg(feature = "std")]
+    pub fn shared_tuning(mut self, tuning: &SharedTuning) -> Self {
+        self.tuning = TuningSource::shared(tuning);
+        self
+    }
+
+    /// Creates a channel with these options.
+    pub fn build<T>(&self) -> (Sender<T>, Receiver<T>) {
+        with_tuning(|| self.tuning.clone())
+    }
+
+    /// Creates a channel with these options, returning [`AllocError`]
+    /// instead of aborting if allocating it fails; see [`try_channel`].
+    pub fn try_build<T>(&self) -> Result<TryChannel<T>, AllocError> {
+        Ok(self.build_in(TryArc::try_new(ChannelState::new())?))
+    }
+
+    /// Creates a channel with these options over a [`ChannelState`] stored
+    /// by the caller; see [`channel_in`].
+    pub fn build_in<T, S: Deref<Target = ChannelState<T>> + Clone>(
+        &self,
+        state: S,
+    ) -> (Sender<T, S>, Receiver<T, S>) {
+        from_shared(state, || self.tuning.clone())
+    }
+}
+
+impl Builder {
+    /// Creates `n` independent channels with these options; see
+    /// [`channels`].
+    pub fn build_many<T>(&self, n: usize) -> Vec<(Sender<T>, Receiver<T>)> {
+        (0..n).map(|_| self.build()).collect()
+    }
+
+    /// Wires up a channel between every ordered pair of `k` nodes with these
+    /// options; see [`mesh`].
+    pub fn build_mesh<T>(&self, k: usize) -> Vec<MeshNode<T>> {
+        mesh_with(k, |_, _| self.build())
+    }
+}
+
+/// Builds a mesh of `k` nodes, creating the channel from node `from` to node
+/// `to` with `channel(from, to)`.
+pub(crate) fn mesh_with<T>(
+    k: usize,
+    mut channel: impl FnMut(usize, usize) -> (Sender<T>, Receiver<T>),
+) -> Vec<MeshNode<T>> {
+    let mut nodes: Vec<MeshNode<T>> = (0..k)
+        .map(|id| MeshNode {
+            id,
+            senders: (0..k).map(|_| None).collect(),
+            receivers: (0..k).map(|_| None).collect(),
+        })
+        .collect();
+    for from in 0..k {
+        for to in (0..k).filter(|&to| to != from) {
+            let (tx, rx) = channel(from, to);
+            nodes[from].senders[to] = Some(tx);
+            nodes[to].receivers[from] = Some(rx);
+        }
+    }
+    nodes
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn with_tuning<T>(tuning: impl Fn() -> TuningSource) -> (Sender<T>, Receiver<T>) {
+    from_shared(Arc::new(ChannelState::new()), tuning)
+}
+
+/// Creates `n` independent single-slot channels, e.g. one per worker.
+pub fn channels<T>(n: usize) -> Vec<(Sender<T>, Receiver<T>)> {
+    Builder::new().build_many(n)
+}
+
+/// Creates a full mesh of channels between `k` nodes, such as the threads of
+/// a thread-per-core design: node `i` can send to and receive from every
+/// other node over a channel of its own per direction.
+///
+/// Returns the nodes in order; move each to its thread.
+///
+/// ```
+/// let mut nodes = waitx::mesh::<usize>(3).into_iter();
+/// let (a, b, c) = (nodes.next().unwrap(), nodes.next().unwrap(), nodes.next().unwrap());
+///
+/// a.sender(2).unwrap().send(a.id());
+/// b.sender(2).unwrap().send(b.id());
+/// assert_eq!(c.receiver(0).unwrap().recv(), 0);
+/// assert_eq!(c.receiver(1).unwrap().recv(), 1);
+/// assert!(c.sender(2).is_none());
+/// ```
+pub fn mesh<T>(k: usize) -> Vec<MeshNode<T>> {
+    Builder::new().build_mesh(k)
+}
+
+/// One node's endpoints in a [`mesh`].
+pub struct MeshNode<T> {
+    id: usize,
+    /// Indexed by peer; `None` at the node's own index.
+    senders: Vec<Option<Sender<T>>>,
+    receivers: Vec<Option<Receiver<T>>>,
+}
+
+impl<T> MeshNode<T> {
+    /// Returns the node's index in the mesh.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns how many nodes the mesh has, this one included.
+    pub fn len(&self) -> usize {
+        self.senders.len()
+    }
+
+    /// Returns whether the mesh has no nodes; never true for a node.
+    pub fn is_empty(&self) -> bool {
+        self.senders.is_empty()
+    }
+
+    /// Returns the sender to node `peer`, or `None` for this node itself or
+    /// out of range.
+    pub fn sender(&self, peer: usize) -> Option<&Sender<T>> {
+        self.senders.get(peer)?.as_ref()
+    }
+
+    /// Returns the receiver from node `peer`, or `None` for this node itself
+    /// or out of range.
+    pub fn receiver(&self, peer: usize) -> Option<&Receiver<T>> {
+        self.receivers.get(peer)?.as_ref()
+    }
+}
+
+/// Creates a single-slot synchronous channel over a [`ChannelState`] stored
+/// by the caller, such as a `&'static ChannelState` or one allocated in an
+/// arena.
+///
+/// Each `state` must back a single channel. Its blocking operations use the
+/// calling thread's tuning, like [`channel`].
+///
+/// ```
+/// let state = waitx::ChannelState::new();
+/// let (tx, rx) = waitx::channel_in(&state);
+///
+/// std::thread::scope(|s| {
+///     s.spawn(move || tx.send(42));
+///     assert_eq!(rx.recv(), 42);
+/// });
+/// ```
+pub fn channel_in<T, S: Deref<Target = ChannelState<T>> + Clone>(
+    state: S,
+) -> (Sender<T, S>, Receiver<T, S>) {
+    from_shared(state, || TuningSource::Thread)
+}
+
+/// A channel whose shared state was allocated by [`try_channel`].
+pub type TryChannel<T> = (
+    Sender<T, TryArc<ChannelState<T>>>,
+    Receiver<T, TryArc<ChannelState<T>>>,
+);
+
+/// Creates a new single-slot synchronous channel like [`channel`], but
+/// returns [`AllocError`] instead of aborting if allocating it fails.
+pub fn try_channel<T>() -> Result<TryChannel<T>, AllocError> {
+    Ok(channel_in(TryArc::try_new(ChannelState::new())?))
+}
+
+fn from_shared<T, S: Deref<Target = ChannelState<T>> + Clone>(
+    shared: S,
+    tuning: impl Fn() -> TuningSource,
+) -> (Sender<T, S>, Receiver<T, S>) {
+    let tx = Sender(
+        Inner {
+            shared: shared.clone(),
+            state: WaitState::new(tuning()),
+        },
+        PhantomData,
+    );
+    let rx = Receiver(
+        Inner {
+            shared,
+            state: WaitState::new(tuning()),
+        },
+        PhantomData,
+    );
+    (tx, rx)
+}
+
+/// A block of channels allocated together, one per worker, each on cache
+/// lines of its own.
+///
+/// [`ChannelState`] is aligned to (and sized in multiples of) the cache line
+/// pair the CPU prefetches together, so neighbouring channels never share a
+/// line and one worker's traffic doesn't stall the next one's. The
+/// endpoints keep their own wait state inline; move each into the thread
+/// that uses it rather than keeping them side by side.
+///
+/// ```
+/// let mut arena = waitx::ChannelArena::new(4);
+///
+/// std::thread::scope(|s| {
+///     for (i, (tx, rx)) in arena.channels().enumerate() {
+///         s.spawn(move || tx.send(i));
+///         s.spawn(move || assert_eq!(rx.recv(), i));
+///     }
+/// });
+/// ```
+pub struct ChannelArena<T> {
+    states: Box<[ChannelState<T>]>,
+}
+
+/// Both halves of a channel in a [`ChannelArena`].
+type Endpoints<'a, T> = (
+    Sender<T, &'a ChannelState<T>>,
+    Receiver<T, &'a ChannelState<T>>,
+);
+
+impl<T> ChannelArena<T> {
+    /// Allocates `len` channels in one block.
+    pub fn new(len: usize) -> Self {
+        Self {
+            states: (0..len).map(|_| ChannelState::new()).collect(),
+        }
+    }
+
+    /// Returns how many channels the arena holds.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Returns whether the arena holds no channels.
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Resets every channel to empty and returns their endpoints, in order.
+    ///
+    /// A value left in a channel from a previous round is dropped.
+    pub fn channels(&mut self) -> impl Iterator<Item = Endpoints<'_, T>> {
+        self.states.iter_mut().map(|state| {
+            state.reset();
+            channel_in(&*state)
+        })
+    }
+}
+
+/// Recycles the allocations of finished channels, so creating a channel from
+/// the pool doesn't touch the allocator in the steady state.
+///
+/// Suits request/response patterns that create many short-lived channels.
+/// Channels from the pool use the calling thread's tuning, like [`channel`].
+#[cfg(feature = "std")]
+pub struct ChannelPool<T> {
+    free: std::sync::Mutex<Vec<Arc<ChannelState<T>>>>,
+}
+
+#[cfg(feature = "std")]
+impl<T> ChannelPool<T> {
+    /// Creates an empty pool.
+    pub fn new() -> Self {
+        Self {
+            free: std::sync::Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Creates a channel, reusing a recycled allocation if one is available.
+    pub fn channel(&self) -> (Sender<T>, Receiver<T>) {
+        let shared = self.free.lock().unwrap().pop();
+        let shared = shared.unwrap_or_else(|| Arc::new(ChannelState::new()));
+        from_shared(shared, || TuningSource::Thread)
+    }
+
+    /// Returns a channel's allocation to the pool, resetting it to a fresh
+    /// empty channel.
+    ///
+    /// A value still in the slot is dropped. Halves of two different
+    /// channels are just dropped, closing them.
+    pub fn recycle(&self, tx: Sender<T>, rx: Receiver<T>) {
+        if !tx.same_channel(&rx) {
+            return;
+        }
+        let Inner { mut shared, .. } = tx.take_inner();
+        drop(rx.take_inner());
+
+        if let Some(state) = Arc::get_mut(&mut shared) {
+            state.reset();
+            self.free.lock().unwrap().push(shared);
+        }
+    }
+
+    /// Returns how many allocations are ready for reuse.
+    pub fn available(&self) -> usize {
+        self.free.lock().unwrap().len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Default for ChannelPool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shared state of a [`loaned_channel`]: one value that lives as long as the
+/// channel and is lent to each side in turn.
+struct LoanShared<T> {
+    value: UnsafeCell<T>,
+    /// Signaled by the sender once it committed the value.
+    full: Event,
+    /// Signaled by the receiver once it released the value.
+    empty: Event,
+}
+
+unsafe impl<T: Send> Send for LoanShared<T> {}
+unsafe impl<T: Send> Sync for LoanShared<T> {}
+
+/// Sending half of a [`loaned_channel`].
+pub struct LoanSender<T> {
+    shared: Arc<LoanShared<T>>,
+    state: WaitState,
+}
+
+impl<T> LoanSender<T> {
+    /// Blocks until the receiver released the slot and lends it out for
+    /// writing.
+    ///
+    /// The slot still holds the previous message; overwrite what is needed
+    /// and [`commit`](SendLoan::commit). Dropping the loan without committing
+    /// hands the slot back unsent.
+    #[inline]
+    pub fn loan(&self) -> SendLoan<'_, T> {
+        self.state.wait(&self.shared.empty);
+        SendLoan { tx: self }
+    }
+}
+
+/// The slot of a [`loaned_channel`], lent to the sender.
+pub struct SendLoan<'a, T> {
+    tx: &'a LoanSender<T>,
+}
+
+impl<T> SendLoan<'_, T> {
+    /// Publishes the slot to the receiver.
+    #[inline]
+    pub fn commit(self) {
+        self.tx.shared.full.signal();
+        core::mem::forget(self);
+    }
+}
+
+impl<T> Deref for SendLoan<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // SAFETY: the receiver doesn't touch the slot while it is lent here.
+        unsafe { &*self.tx.shared.value.get() }
+    }
+}
+
+impl<T> core::ops::DerefMut for SendLoan<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: as above, and only one loan is out at a time.
+        unsafe { &mut *self.tx.shared.value.get() }
+    }
+}
+
+impl<T> Drop for SendLoan<'_, T> {
+    fn drop(&mut self) {
+        self.tx.shared.empty.signal();
+    }
+}
+
+/// Receiving half of a [`loaned_channel`].
+pub struct LoanReceiver<T> {
+    shared: Arc<LoanShared<T>>,
+    state: WaitState,
+}
+
+impl<T> LoanReceiver<T> {
+    /// Blocks until the sender committed a message and lends it out for
+    /// reading. Dropping the loan releases the slot to the sender.
+    #[inline]
+    pub fn recv(&self) -> RecvLoan<'_, T> {
+        self.state.wait(&self.shared.full);
+        RecvLoan { rx: self }
+    }
+}
+
+/// A message of a [`loaned_channel`], lent to the receiver.
+pub struct RecvLoan<'a, T> {
+    rx: &'a LoanReceiver<T>,
+}
+
+impl<T> Deref for RecvLoan<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // SAFETY: the sender doesn't touch the slot until it is released.
+        unsafe { &*self.rx.shared.value.get() }
+    }
+}
+
+impl<T> Drop for RecvLoan<'_, T> {
+    fn drop(&mut self) {
+        self.rx.shared.empty.signal();
+    }
+}
+
+/// Creates a channel that passes messages in a single slot, initialized to
+/// `value`, which both sides borrow instead of moving values in and out.
+///
+/// The sender fills the slot in place through [`LoanSender::loan`] and the
+/// receiver reads it through [`LoanReceiver::recv`], so a multi-kilobyte
+/// message is never copied. The slot is never reset: each loan sees what the
+/// previous message left in it.
+pub fn loaned_channel<T>(value: T) -> (LoanSender<T>, LoanReceiver<T>) {
+    let shared = Arc::new(LoanShared {
+        value: UnsafeCell::new(value),
+        full: Event::new(),
+        empty: Event::new(),
+    });
+    shared.empty.signal(); // the slot starts lent to nobody
+
+    let tx = LoanSender {
+        shared: shared.clone(),
+        state: WaitState::new(TuningSource::Thread),
     };
-    let inner_rx = Inner {
-        slot: slot_rx,
-        tx: tx_2,
-        rx: rx_1,
+    let rx = LoanReceiver {
+        shared,
+        state: WaitState::new(TuningSource::Thread),
     };
-
-    let (tx, rx) = (Sender(inner_tx), Receiver(inner_rx));
-    rx.0.tx.signal(); // initialize sender: slot starts empty
     (tx, rx)
 }
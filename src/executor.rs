@@ -0,0 +1,48 @@
+//! Driving a single future to completion on the current thread.
+
+use crate::pair::{Waker, pair_coalescing};
+
+use alloc::sync::Arc;
+use core::future::Future;
+use core::task::{Context, Poll};
+
+/// Wakes the blocked [`block_on`] through its pair.
+struct Unpark(Waker);
+
+impl alloc::task::Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.signal();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.signal();
+    }
+}
+
+/// Runs `future` to completion on the current thread, blocking on a
+/// [`Waiter`](crate::Waiter) between polls.
+///
+/// A tiny executor for a single future, without pulling in one from another
+/// crate: it polls, and waits with this crate's tuned spin-then-park until
+/// the future's waker fires. Wakes while the future is being polled coalesce
+/// into a single re-poll.
+///
+/// ```
+/// let (tx, rx) = waitx::channel();
+/// std::thread::spawn(move || tx.send(7));
+///
+/// let value = waitx::block_on(async move { rx.recv() + 1 });
+/// assert_eq!(value, 8);
+/// ```
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let (waker, waiter) = pair_coalescing();
+    let waker = core::task::Waker::from(Arc::new(Unpark(waker)));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = core::pin::pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        waiter.wait();
+    }
+}
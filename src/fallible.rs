@@ -0,0 +1,105 @@
+//! Construction that reports allocation failure instead of aborting.
+//!
+//! [`try_pair`](crate::try_pair) and [`try_channel`](crate::try_channel)
+//! allocate their shared state through [`TryArc`], a reference-counted
+//! pointer whose constructor returns [`AllocError`] when the allocator does.
+
+use core::alloc::Layout;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ptr::NonNull;
+use core::sync::atomic::{self, AtomicUsize, Ordering};
+
+/// Error returned when the allocator fails to provide memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError(());
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AllocError {}
+
+struct ArcBox<T> {
+    count: AtomicUsize,
+    value: T,
+}
+
+/// A shared, reference-counted pointer like `Arc`, created fallibly with
+/// [`TryArc::try_new`].
+pub struct TryArc<T> {
+    ptr: NonNull<ArcBox<T>>,
+    _owns: PhantomData<ArcBox<T>>,
+}
+
+// SAFETY: like `Arc`, the value is shared between threads and dropped by
+// whichever drops the last reference.
+unsafe impl<T: Send + Sync> Send for TryArc<T> {}
+unsafe impl<T: Send + Sync> Sync for TryArc<T> {}
+
+impl<T> TryArc<T> {
+    /// Moves `value` into a new allocation, or returns [`AllocError`] if the
+    /// allocator fails.
+    pub fn try_new(value: T) -> Result<Self, AllocError> {
+        let layout = Layout::new::<ArcBox<T>>();
+        // SAFETY: the layout isn't zero-sized, it holds the count.
+        let ptr = unsafe { alloc::alloc::alloc(layout) }.cast::<ArcBox<T>>();
+        let ptr = NonNull::new(ptr).ok_or(AllocError(()))?;
+        // SAFETY: freshly allocated for an `ArcBox<T>`.
+        unsafe {
+            ptr.as_ptr().write(ArcBox {
+                count: AtomicUsize::new(1),
+                value,
+            });
+        }
+        Ok(Self {
+            ptr,
+            _owns: PhantomData,
+        })
+    }
+
+    #[inline(always)]
+    fn inner(&self) -> &ArcBox<T> {
+        // SAFETY: the allocation lives as long as any reference to it.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> Clone for TryArc<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.inner().count.fetch_add(1, Ordering::Relaxed);
+        Self {
+            ptr: self.ptr,
+            _owns: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for TryArc<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.inner().value
+    }
+}
+
+impl<T> Drop for TryArc<T> {
+    fn drop(&mut self) {
+        if self.inner().count.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+        // pairs with the release above in every other reference's drop
+        atomic::fence(Ordering::Acquire);
+        // SAFETY: this was the last reference.
+        unsafe {
+            core::ptr::drop_in_place(self.ptr.as_ptr());
+            alloc::alloc::dealloc(self.ptr.as_ptr().cast(), Layout::new::<ArcBox<T>>());
+        }
+    }
+}
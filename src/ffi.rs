@@ -0,0 +1,284 @@
+//! C FFI surface for the channel and pair APIs.
+//!
+//! Every handle is an opaque heap object created by a `*_new` function and
+//! released with the matching `*_free` function. Channels carry `void*`
+//! payloads; ownership of whatever they point to is up to the caller.
+//!
+//! The C header lives in `include/waitx.h` and is generated with `cbindgen`.
+//! To link from C, build a static or dynamic library:
+//!
+//! ```sh
+//! cbindgen --config cbindgen.toml --output include/waitx.h
+//! cargo rustc --release --features ffi --crate-type staticlib
+//! ```
+
+use alloc::boxed::Box;
+use core::ffi::c_void;
+
+use crate::{Receiver, Sender, Waiter, Waker};
+
+/// A `void*` payload; the channel never dereferences it.
+#[derive(Clone, Copy)]
+struct Payload(*mut c_void);
+
+// SAFETY: the pointer is only moved between threads, never dereferenced.
+unsafe impl Send for Payload {}
+
+/// Sending half of a channel of `void*` values.
+pub struct WaitxSender(Sender<Payload>);
+
+/// Receiving half of a channel of `void*` values.
+pub struct WaitxReceiver(Receiver<Payload>);
+
+/// Signaling half of a counted notification pair.
+pub struct WaitxWaker(Waker);
+
+/// Waiting half of a counted notification pair.
+pub struct WaitxWaiter(Waiter);
+
+/// Creates a new single-slot channel, storing its endpoints in `tx` and `rx`.
+///
+/// # Safety
+///
+/// `tx` and `rx` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_channel_new(tx: *mut *mut WaitxSender, rx: *mut *mut WaitxReceiver) {
+    let (sender, receiver) = crate::channel();
+    unsafe {
+        tx.write(Box::into_raw(Box::new(WaitxSender(sender))));
+        rx.write(Box::into_raw(Box::new(WaitxReceiver(receiver))));
+    }
+}
+
+/// Sends `value`, blocking until the slot is empty.
+///
+/// Aborts if the channel is closed, which freeing the receiver does too; see
+/// [`waitx_send_unless_closed`].
+///
+/// # Safety
+///
+/// `tx` must be a live sender from [`waitx_channel_new`], used by one thread
+/// at a time.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_send(tx: *const WaitxSender, value: *mut c_void) {
+    unsafe { (*tx).0.send(Payload(value)) };
+}
+
+/// Sends `value`, blocking until the slot is empty. Returns `false` if the
+/// channel is or gets closed first, e.g. because the receiver was freed.
+///
+/// # Safety
+///
+/// `tx` must be a live sender from [`waitx_channel_new`], used by one thread
+/// at a time.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_send_unless_closed(
+    tx: *const WaitxSender,
+    value: *mut c_void,
+) -> bool {
+    unsafe { (*tx).0.send_unless_closed(Payload(value)).is_ok() }
+}
+
+/// Sends `value` if the slot is empty. Returns `false` if it is full or the
+/// channel closed.
+///
+/// # Safety
+///
+/// `tx` must be a live sender from [`waitx_channel_new`], used by one thread
+/// at a time.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_try_send(tx: *const WaitxSender, value: *mut c_void) -> bool {
+    unsafe { (*tx).0.try_send(Payload(value)).is_ok() }
+}
+
+/// Receives a value, blocking until one is available.
+///
+/// Aborts if the channel is closed, which freeing the sender does too, and
+/// no value is left; see [`waitx_recv_unless_closed`].
+///
+/// # Safety
+///
+/// `rx` must be a live receiver from [`waitx_channel_new`], used by one thread
+/// at a time.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_recv(rx: *const WaitxReceiver) -> *mut c_void {
+    unsafe { (*rx).0.recv().0 }
+}
+
+/// Receives a value into `out`, blocking until one is available. Returns
+/// `false` once the channel is closed and empty, e.g. because the sender
+/// was freed, leaving `out` untouched.
+///
+/// # Safety
+///
+/// `rx` must be a live receiver from [`waitx_channel_new`], used by one thread
+/// at a time, and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_recv_unless_closed(
+    rx: *const WaitxReceiver,
+    out: *mut *mut c_void,
+) -> bool {
+    match unsafe { (*rx).0.recv_unless_closed() } {
+        Ok(value) => {
+            unsafe { out.write(value.0) };
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Receives a value into `out` if one is available. Returns `false` if the
+/// slot is empty, leaving `out` untouched.
+///
+/// # Safety
+///
+/// `rx` must be a live receiver from [`waitx_channel_new`], used by one thread
+/// at a time, and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_try_recv(rx: *const WaitxReceiver, out: *mut *mut c_void) -> bool {
+    match unsafe { (*rx).0.try_recv() } {
+        Some(value) => {
+            unsafe { out.write(value.0) };
+            true
+        }
+        None => false,
+    }
+}
+
+/// Closes the channel: a value still in the slot can be received, then
+/// receives fail. A blocked receiver is woken.
+///
+/// # Safety
+///
+/// `tx` must be a live sender from [`waitx_channel_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_sender_close(tx: *const WaitxSender) {
+    unsafe { (*tx).0.close() };
+}
+
+/// Closes the channel: a value still in the slot can be received, then
+/// sends fail. A blocked sender is woken.
+///
+/// # Safety
+///
+/// `rx` must be a live receiver from [`waitx_channel_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_receiver_close(rx: *const WaitxReceiver) {
+    unsafe { (*rx).0.close() };
+}
+
+/// Releases a sender, closing the channel. Passing `NULL` is a no-op.
+///
+/// # Safety
+///
+/// `tx` must be `NULL` or a sender from [`waitx_channel_new`] not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_sender_free(tx: *mut WaitxSender) {
+    if !tx.is_null() {
+        drop(unsafe { Box::from_raw(tx) });
+    }
+}
+
+/// Releases a receiver, closing the channel. Passing `NULL` is a no-op.
+///
+/// # Safety
+///
+/// `rx` must be `NULL` or a receiver from [`waitx_channel_new`] not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_receiver_free(rx: *mut WaitxReceiver) {
+    if !rx.is_null() {
+        drop(unsafe { Box::from_raw(rx) });
+    }
+}
+
+/// Creates a new counted notification pair, storing its halves in `waker`
+/// and `waiter`.
+///
+/// # Safety
+///
+/// `waker` and `waiter` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_pair_new(
+    waker: *mut *mut WaitxWaker,
+    waiter: *mut *mut WaitxWaiter,
+) {
+    let (wk, wt) = crate::pair();
+    unsafe {
+        waker.write(Box::into_raw(Box::new(WaitxWaker(wk))));
+        waiter.write(Box::into_raw(Box::new(WaitxWaiter(wt))));
+    }
+}
+
+/// Returns a new waker signaling the same waiter.
+///
+/// # Safety
+///
+/// `waker` must be a live waker.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_waker_clone(waker: *const WaitxWaker) -> *mut WaitxWaker {
+    Box::into_raw(Box::new(WaitxWaker(unsafe { (*waker).0.clone() })))
+}
+
+/// Increments the event counter and wakes the waiting thread.
+///
+/// # Safety
+///
+/// `waker` must be a live waker.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_waker_signal(waker: *const WaitxWaker) {
+    unsafe { (*waker).0.signal() };
+}
+
+/// Wakes the waiter only if it is currently blocked.
+///
+/// # Safety
+///
+/// `waker` must be a live waker.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_waker_poke(waker: *const WaitxWaker) {
+    unsafe { (*waker).0.poke() };
+}
+
+/// Blocks until the next notification.
+///
+/// # Safety
+///
+/// `waiter` must be a live waiter.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_waiter_wait(waiter: *const WaitxWaiter) {
+    unsafe { (*waiter).0.wait() };
+}
+
+/// Consumes a notification if one is pending. Returns `false` otherwise.
+///
+/// # Safety
+///
+/// `waiter` must be a live waiter.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_waiter_try_wait(waiter: *const WaitxWaiter) -> bool {
+    unsafe { (*waiter).0.try_wait() }
+}
+
+/// Releases a waker. Passing `NULL` is a no-op.
+///
+/// # Safety
+///
+/// `waker` must be `NULL` or a waker not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_waker_free(waker: *mut WaitxWaker) {
+    if !waker.is_null() {
+        drop(unsafe { Box::from_raw(waker) });
+    }
+}
+
+/// Releases a waiter. Passing `NULL` is a no-op.
+///
+/// # Safety
+///
+/// `waiter` must be `NULL` or a waiter not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn waitx_waiter_free(waiter: *mut WaitxWaiter) {
+    if !waiter.is_null() {
+        drop(unsafe { Box::from_raw(waiter) });
+    }
+}
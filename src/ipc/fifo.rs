@@ -0,0 +1,62 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+use std::ffi::CString;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+/// A named FIFO used as a doorbell, opened by both processes.
+///
+/// Darwin has no process-shared futex, so the wake word is only used to skip
+/// the wait when a signal already arrived. Every ring writes one byte; a byte
+/// written before the waiter reaches `poll` keeps the FIFO readable, so no
+/// wakeup is lost in between.
+pub struct Bell(OwnedFd);
+
+impl Bell {
+    pub fn open(name: &str, lane: &str) -> std::io::Result<Self> {
+        let path = CString::new(format!("{name}.{lane}"))
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        if unsafe { libc::mkfifo(path.as_ptr(), 0o600) } < 0 {
+            let err = std::io::Error::last_os_error();
+            if err.kind() != std::io::ErrorKind::AlreadyExists {
+                return Err(err);
+            }
+        }
+        // read-write so opening never blocks waiting for the peer
+        let fd = unsafe {
+            libc::open(
+                path.as_ptr(),
+                libc::O_RDWR | libc::O_NONBLOCK | libc::O_CLOEXEC,
+            )
+        };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        // SAFETY: `fd` was just opened and is owned by nobody else.
+        Ok(Self(unsafe { OwnedFd::from_raw_fd(fd) }))
+    }
+
+    #[inline]
+    pub fn ring(&self, _wake: &AtomicU32) {
+        let one: u8 = 1;
+        // only fails with EAGAIN once the FIFO is full, in which case it is
+        // readable anyway.
+        unsafe { libc::write(self.0.as_raw_fd(), (&raw const one).cast(), 1) };
+    }
+
+    #[inline]
+    pub fn wait(&self, wake: &AtomicU32, value: u32) {
+        if wake.load(Ordering::Acquire) != value {
+            return;
+        }
+        let mut pfd = libc::pollfd {
+            fd: self.0.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        unsafe { libc::poll(&mut pfd, 1, -1) };
+
+        // drain so the next wait blocks again; every drained byte belongs to
+        // a signal whose counter update is already visible.
+        let mut buf = [0u8; 64];
+        while unsafe { libc::read(self.0.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len()) } > 0 {}
+    }
+}
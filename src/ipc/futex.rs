@@ -0,0 +1,21 @@
+use core::sync::atomic::AtomicU32;
+
+/// Process-shared futex on the lane's wake word; needs no per-process state.
+pub struct Bell;
+
+impl Bell {
+    #[inline]
+    pub fn open(_name: &str, _lane: &str) -> std::io::Result<Self> {
+        Ok(Self)
+    }
+
+    #[inline]
+    pub fn ring(&self, wake: &AtomicU32) {
+        crate::atomic_wait::wake_one_shared(wake);
+    }
+
+    #[inline]
+    pub fn wait(&self, wake: &AtomicU32, value: u32) {
+        crate::atomic_wait::wait_shared(wake, value);
+    }
+}
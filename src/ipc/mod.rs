@@ -0,0 +1,278 @@
+//! A cross-process single-slot channel over shared memory.
+//!
+//! The slot, both event counters and their wake words live in a caller-provided
+//! memory region (e.g. a `MAP_SHARED` mapping, a POSIX shared memory object or
+//! a Windows file mapping) which two processes map.
+//!
+//! Blocking is platform-specific:
+//! - Linux/Android/FreeBSD: process-shared futexes on the region itself.
+//! - Windows: named auto-reset events `{name}.items` and `{name}.space`.
+//! - macOS/iOS: named FIFOs at the paths `{name}.items` and `{name}.space`,
+//!   created on first use and left in place for the caller to remove.
+//!
+//! Both processes pass the same `name` when attaching their endpoint; it is
+//! ignored where the region alone suffices.
+//!
+//! Only `Copy` payloads are supported: the value is copied bit-for-bit into
+//! the region and must not contain pointers into either address space.
+//!
+//! # Example
+//!
+//! ```
+//! use std::ptr::NonNull;
+//!
+//! // In practice this is a mapping shared between two processes.
+//! let layout = waitx::ipc::layout::<u64>();
+//! let mem = NonNull::new(unsafe { std::alloc::alloc_zeroed(layout) }).unwrap();
+//!
+//! let name = if cfg!(windows) { "Local\\waitx-doc" } else { "/tmp/waitx-doc" };
+//!
+//! unsafe { waitx::ipc::init::<u64>(mem) };
+//! let tx = unsafe { waitx::ipc::sender::<u64>(mem, name) }.unwrap();
+//! let rx = unsafe { waitx::ipc::receiver::<u64>(mem, name) }.unwrap();
+//!
+//! tx.send(42);
+//! assert_eq!(rx.recv(), 42);
+//! # drop((tx, rx));
+//! # unsafe { std::alloc::dealloc(mem.as_ptr(), layout) };
+//! ```
+
+use crate::prelude::*;
+use core::alloc::Layout;
+use core::ptr::NonNull;
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+#[path = "futex.rs"]
+mod bell;
+
+#[cfg(target_vendor = "apple")]
+#[path = "fifo.rs"]
+mod bell;
+
+#[cfg(windows)]
+#[path = "windows.rs"]
+mod bell;
+
+use bell::Bell;
+
+/// One direction of notifications, laid out in shared memory.
+#[repr(C)]
+struct Lane {
+    counter: AtomicCount,
+    wake: AtomicU32,
+}
+
+impl Lane {
+    fn new(counter: Count) -> Self {
+        Self {
+            counter: AtomicCount::new(counter),
+            wake: AtomicU32::new(0),
+        }
+    }
+
+    /// Increments the counter and wakes a waiter in any process.
+    #[inline]
+    fn signal(&self, bell: &Bell) {
+        self.counter.fetch_add(1, Ordering::Release);
+        self.wake.fetch_add(1, Ordering::Release);
+        bell.ring(&self.wake);
+    }
+
+    /// Blocks until the counter reaches `target`.
+    #[inline]
+    fn wait(&self, target: Count, tuning: Tuning, bell: &Bell) {
+        if reached(self.counter.load(Ordering::Acquire), target) {
+            return;
+        }
+        wait_until_with_park(
+            || reached(self.counter.load(Ordering::Acquire), target),
+            &self.wake,
+            tuning,
+            |wake, val| bell.wait(wake, val),
+        );
+    }
+
+    #[inline]
+    fn is_reached(&self, target: Count) -> bool {
+        reached(self.counter.load(Ordering::Acquire), target)
+    }
+}
+
+/// The state placed in the shared region.
+#[repr(C)]
+struct Shared<T> {
+    /// Signaled by the sender once the slot is full.
+    items: Lane,
+    /// Signaled by the receiver once the slot is empty.
+    space: Lane,
+    slot: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// Returns the size and alignment the shared region must have for `T`.
+pub const fn layout<T: Copy>() -> Layout {
+    Layout::new::<Shared<T>>()
+}
+
+/// Initializes a fresh channel in `mem`.
+///
+/// Must be called exactly once, by one process, before either endpoint is
+/// created.
+///
+/// # Safety
+///
+/// `mem` must be valid for writes of [`layout::<T>()`](layout) bytes, suitably
+/// aligned, and not in use by any endpoint.
+pub unsafe fn init<T: Copy>(mem: NonNull<u8>) {
+    let shared = Shared::<T> {
+        items: Lane::new(0),
+        space: Lane::new(1), // the slot starts empty
+        slot: UnsafeCell::new(MaybeUninit::uninit()),
+    };
+    unsafe { mem.cast::<Shared<T>>().write(shared) };
+}
+
+/// Attaches the sending endpoint to a channel initialized with [`init`].
+///
+/// Fails if the platform's wake objects for `name` cannot be opened.
+///
+/// # Safety
+///
+/// `mem` must point to a region initialized by [`init::<T>`](init) that stays
+/// mapped for the lifetime of the returned sender, and at most one sender may
+/// be attached to it across all processes.
+pub unsafe fn sender<T: Copy>(mem: NonNull<u8>, name: &str) -> std::io::Result<Sender<T>> {
+    Ok(Sender(Endpoint::new(
+        mem,
+        Bell::open(name, "space")?,
+        Bell::open(name, "items")?,
+    )))
+}
+
+/// Attaches the receiving endpoint to a channel initialized with [`init`].
+///
+/// Fails if the platform's wake objects for `name` cannot be opened.
+///
+/// # Safety
+///
+/// `mem` must point to a region initialized by [`init::<T>`](init) that stays
+/// mapped for the lifetime of the returned receiver, and at most one receiver
+/// may be attached to it across all processes.
+pub unsafe fn receiver<T: Copy>(mem: NonNull<u8>, name: &str) -> std::io::Result<Receiver<T>> {
+    Ok(Receiver(Endpoint::new(
+        mem,
+        Bell::open(name, "items")?,
+        Bell::open(name, "space")?,
+    )))
+}
+
+struct Endpoint<T> {
+    shared: NonNull<Shared<T>>,
+    next: AtomicCount,
+    /// Wakes this endpoint when the lane it waits on is signaled.
+    wait_bell: Bell,
+    /// Wakes the peer when this endpoint signals its lane.
+    ring_bell: Bell,
+}
+
+impl<T> Endpoint<T> {
+    fn new(mem: NonNull<u8>, wait_bell: Bell, ring_bell: Bell) -> Self {
+        Self {
+            shared: mem.cast(),
+            next: AtomicCount::new(0),
+            wait_bell,
+            ring_bell,
+        }
+    }
+
+    #[inline(always)]
+    fn shared(&self) -> &Shared<T> {
+        // SAFETY: guaranteed by the contract of `sender`/`receiver`.
+        unsafe { self.shared.as_ref() }
+    }
+
+    /// Blocks until `lane` has been signaled once more than already consumed.
+    #[inline]
+    fn wait(&self, lane: &Lane) {
+        let target = self.next.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+        lane.wait(target, Tuning::DEFAULT, &self.wait_bell);
+    }
+
+    /// Consumes one signal from `lane` if available.
+    #[inline]
+    fn try_wait(&self, lane: &Lane) -> bool {
+        let target = self.next.load(Ordering::Relaxed).wrapping_add(1);
+        if lane.is_reached(target) {
+            self.next.store(target, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+// SAFETY: the endpoint only hands `T` values across, see the `T: Send` bound.
+unsafe impl<T: Send> Send for Endpoint<T> {}
+
+/// Sending half of a cross-process single-slot channel.
+pub struct Sender<T>(Endpoint<T>);
+
+impl<T: Copy> Sender<T> {
+    /// Sends a value, blocking indefinitely until the slot becomes empty.
+    #[inline]
+    pub fn send(&self, value: T) {
+        let shared = self.0.shared();
+        self.0.wait(&shared.space);
+        self.put(value);
+    }
+
+    /// Attempts to send a value without blocking, returning it if the slot is full.
+    #[inline]
+    pub fn try_send(&self, value: T) -> Result<(), T> {
+        let shared = self.0.shared();
+        if !self.0.try_wait(&shared.space) {
+            return Err(value);
+        }
+        self.put(value);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn put(&self, value: T) {
+        let shared = self.0.shared();
+        // SAFETY: the slot is empty and owned by the sender until signaled.
+        unsafe { (*shared.slot.get()).write(value) };
+        shared.items.signal(&self.0.ring_bell);
+    }
+}
+
+/// Receiving half of a cross-process single-slot channel.
+pub struct Receiver<T>(Endpoint<T>);
+
+impl<T: Copy> Receiver<T> {
+    /// Receives a value, blocking until one is available.
+    #[inline]
+    pub fn recv(&self) -> T {
+        let shared = self.0.shared();
+        self.0.wait(&shared.items);
+        self.take()
+    }
+
+    /// Attempts to receive a value without blocking.
+    #[inline]
+    pub fn try_recv(&self) -> Option<T> {
+        let shared = self.0.shared();
+        if !self.0.try_wait(&shared.items) {
+            return None;
+        }
+        Some(self.take())
+    }
+
+    #[inline(always)]
+    fn take(&self) -> T {
+        let shared = self.0.shared();
+        // SAFETY: the slot is full and owned by the receiver until signaled.
+        let value = unsafe { (*shared.slot.get()).assume_init_read() };
+        shared.space.signal(&self.0.ring_bell);
+        value
+    }
+}
@@ -0,0 +1,48 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+use windows_sys::Win32::Foundation::{CloseHandle, FALSE, HANDLE};
+use windows_sys::Win32::System::Threading::{
+    CreateEventW, INFINITE, SetEvent, WaitForSingleObject,
+};
+
+/// A named auto-reset event, opened (or created) by both processes.
+///
+/// `WaitOnAddress` only works within a process, so the wake word is only used
+/// to skip the wait when a signal already arrived. A `SetEvent` with no waiter
+/// stays latched until the next wait, so no wakeup is lost in between.
+pub struct Bell(HANDLE);
+
+// SAFETY: event handles may be used from any thread.
+unsafe impl Send for Bell {}
+unsafe impl Sync for Bell {}
+
+impl Bell {
+    pub fn open(name: &str, lane: &str) -> std::io::Result<Self> {
+        let name: Vec<u16> = format!("{name}.{lane}")
+            .encode_utf16()
+            .chain(core::iter::once(0))
+            .collect();
+        let handle = unsafe { CreateEventW(core::ptr::null(), FALSE, FALSE, name.as_ptr()) };
+        if handle.is_null() {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Self(handle))
+    }
+
+    #[inline]
+    pub fn ring(&self, _wake: &AtomicU32) {
+        unsafe { SetEvent(self.0) };
+    }
+
+    #[inline]
+    pub fn wait(&self, wake: &AtomicU32, value: u32) {
+        if wake.load(Ordering::Acquire) == value {
+            unsafe { WaitForSingleObject(self.0, INFINITE) };
+        }
+    }
+}
+
+impl Drop for Bell {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.0) };
+    }
+}
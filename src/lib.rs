@@ -23,16 +23,72 @@
 //! });
 //! waiter.wait();
 //! ```
+//!
+//! # `no_std`
+//!
+//! Disabling the default `std` feature builds the crate on `core` + `alloc`.
+//! Blocking is then delegated to user-supplied [`ParkHooks`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+mod arch;
+#[cfg(all(feature = "std", not(feature = "loom")))]
 mod atomic_wait;
+#[cfg(all(feature = "std", not(feature = "loom")))]
+mod calibrate;
+mod executor;
+mod notify;
 mod prelude;
+#[cfg(all(feature = "priority-boost", windows, not(feature = "loom")))]
+mod priority;
+#[cfg(all(feature = "qos", target_vendor = "apple", not(feature = "loom")))]
+mod qos;
 mod util;
 
 #[cfg(feature = "loom")]
 mod loom;
 
+pub mod aggregator;
 pub mod channel;
+pub mod fallible;
+#[cfg(all(feature = "ffi", not(feature = "loom")))]
+pub mod ffi;
+#[cfg(all(
+    feature = "ipc",
+    not(feature = "loom"),
+    any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_vendor = "apple",
+        windows
+    )
+))]
+pub mod ipc;
+pub mod oneshot;
 pub mod pair;
+pub mod park;
+#[cfg(all(feature = "std", not(feature = "loom")))]
+pub mod pipeline;
+pub mod rpc;
+pub mod session;
+#[cfg(all(feature = "std", unix))]
+pub mod signal;
+pub mod task;
+#[cfg(all(feature = "std", not(feature = "loom")))]
+pub mod topology;
 
+#[cfg(all(feature = "std", not(feature = "loom")))]
+pub use calibrate::{calibrate, shares_core};
 pub use channel::*;
+pub use executor::block_on;
+pub use fallible::{AllocError, TryArc};
+pub use oneshot::*;
 pub use pair::*;
+pub use park::{ParkHooks, SetParkHooksError, set_park_hooks};
+pub use task::*;
+#[cfg(feature = "std")]
+pub use util::{SharedTuning, set_thread_tuning};
+pub use util::{SpinStrategy, Tuning, cpu_relax, set_max_spinners, spin_hint_batch, thread_tuning};
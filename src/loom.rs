@@ -1,10 +1,20 @@
 #[cfg(test)]
 mod loom_tests {
+    use crate::pair::{TuningSource, WaitState};
     use crate::prelude::*;
 
     /// required for loom since it cannot model busy spin loops.
     const PARK_ONLY: Tuning = Tuning::new(0, 0);
 
+    /// runs a model with at most two preemptions, unless
+    /// `LOOM_MAX_PREEMPTIONS` says otherwise, for models whose waits park
+    /// more than once: too many interleavings to explore exhaustively.
+    fn bounded_model(f: impl Fn() + Sync + Send + 'static) {
+        let mut builder = loom::model::Builder::new();
+        builder.preemption_bound.get_or_insert(2);
+        builder.check(f);
+    }
+
     /// signal arrives before wait
     #[test]
     fn pair_signal_before_wait() {
@@ -35,7 +45,7 @@ mod loom_tests {
     /// two sequential signals, two sequential waits.
     #[test]
     fn pair_two_signals_two_waits() {
-        loom::model(|| {
+        bounded_model(|| {
             let (waker, waiter) = pair();
             let waker = Arc::new(waker);
             let w = waker.clone();
@@ -91,19 +101,22 @@ mod loom_tests {
         });
     }
 
-    /// exposes the TOCTOU liveness bug in `wake()`.
+    /// a single poke after the waiter's stop condition was set is never
+    /// lost: the waiter counts as waiting before it checks the condition.
     #[test]
     fn pair_wake_liveness_race() {
         loom::model(|| {
-            let (waker, waiter) = pair();
-            let waker = Arc::new(waker);
-            let w = waker.clone();
+            let event = Arc::new(Event::new());
+            let ready = Arc::new(AtomicBool::new(false));
+            let (e, r) = (event.clone(), ready.clone());
 
             let t = thread::spawn(move || {
-                w.poke();
+                r.store(true, Ordering::SeqCst);
+                e.poke();
             });
 
-            waiter.wait_with(PARK_ONLY);
+            let state = WaitState::new(TuningSource::Fixed(PARK_ONLY));
+            state.wait_unless(&event, PARK_ONLY, || ready.load(Ordering::SeqCst));
             t.join().unwrap();
         });
     }
@@ -134,7 +147,7 @@ mod loom_tests {
     /// sender parks waiting for the slot to empty; receiver consumes concurrently.
     #[test]
     fn channel_send_parks_waiting_for_drain() {
-        loom::model(|| {
+        bounded_model(|| {
             let (tx, rx) = channel::<u8>();
 
             let t = thread::spawn(move || {
@@ -151,7 +164,7 @@ mod loom_tests {
     /// two round trips
     #[test]
     fn channel_ping_pong_two_rounds() {
-        loom::model(|| {
+        bounded_model(|| {
             let (tx, rx) = channel::<u8>();
 
             let t = thread::spawn(move || {
@@ -265,7 +278,7 @@ mod loom_tests {
     /// validates that the `Slot`'s `mark_full` Release and `recv`'s `is_full`.
     #[test]
     fn channel_slot_write_visible_after_recv() {
-        loom::model(|| {
+        bounded_model(|| {
             let (tx, rx) = channel::<[u8; 4]>();
 
             let t = thread::spawn(move || {
@@ -0,0 +1,173 @@
+//! Out-of-band notification backends.
+//!
+//! A pair may carry a [`Notifier`] that is triggered on every signal, in
+//! addition to the counter and wake word, so the notification can be observed
+//! by an external event loop. Without any backend feature enabled the enum is
+//! empty and costs nothing.
+
+#[cfg(any(
+    all(feature = "eventfd", any(target_os = "linux", target_os = "android")),
+    all(feature = "fd", unix)
+))]
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+use io_uring::{IoUring, opcode, squeue, types};
+
+pub(crate) enum Notifier {
+    /// A non-blocking, non-semaphore `eventfd` incremented on every signal.
+    #[cfg(all(feature = "eventfd", any(target_os = "linux", target_os = "android")))]
+    EventFd(OwnedFd),
+    /// A non-blocking self-pipe, written one byte per signal.
+    #[cfg(all(
+        feature = "fd",
+        unix,
+        not(any(target_os = "linux", target_os = "android"))
+    ))]
+    Pipe { read: OwnedFd, write: OwnedFd },
+    /// A private submission ring posting an `IORING_OP_MSG_RING` completion
+    /// carrying `user_data` to the `target` ring.
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    MsgRing {
+        ring: Box<std::sync::Mutex<IoUring>>,
+        target: std::os::fd::RawFd,
+        user_data: u64,
+    },
+}
+
+impl Notifier {
+    /// Creates a new `eventfd` notifier.
+    #[cfg(all(feature = "eventfd", any(target_os = "linux", target_os = "android")))]
+    pub fn eventfd() -> std::io::Result<Self> {
+        let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        // SAFETY: `fd` was just created and is owned by nobody else.
+        Ok(Self::EventFd(unsafe { OwnedFd::from_raw_fd(fd) }))
+    }
+
+    /// Creates a new `IORING_OP_MSG_RING` notifier targeting `target`.
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    pub fn msg_ring(target: std::os::fd::RawFd, user_data: u64) -> std::io::Result<Self> {
+        Ok(Self::MsgRing {
+            ring: Box::new(std::sync::Mutex::new(IoUring::new(4)?)),
+            target,
+            user_data,
+        })
+    }
+
+    /// Creates a new non-blocking self-pipe notifier.
+    #[cfg(all(
+        feature = "fd",
+        unix,
+        not(any(target_os = "linux", target_os = "android"))
+    ))]
+    pub fn pipe() -> std::io::Result<Self> {
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        // SAFETY: both ends were just created and are owned by nobody else.
+        let (read, write) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+        for fd in [&read, &write] {
+            let fd = fd.as_raw_fd();
+            unsafe {
+                if libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) < 0
+                    || libc::fcntl(fd, libc::F_SETFL, libc::O_NONBLOCK) < 0
+                {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+        }
+        Ok(Self::Pipe { read, write })
+    }
+
+    /// Creates the platform's preferred pollable notifier: an `eventfd` on
+    /// Linux/Android, a self-pipe on other unix platforms.
+    #[cfg(all(feature = "fd", unix))]
+    pub fn pollable() -> std::io::Result<Self> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        return Self::eventfd();
+
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        return Self::pipe();
+    }
+
+    /// Returns the descriptor that becomes readable when notified, if any.
+    #[cfg(all(feature = "fd", unix))]
+    pub fn as_raw_fd(&self) -> Option<std::os::fd::RawFd> {
+        match *self {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Self::EventFd(ref fd) => Some(fd.as_raw_fd()),
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            Self::Pipe { ref read, .. } => Some(read.as_raw_fd()),
+            #[cfg(all(feature = "io-uring", target_os = "linux"))]
+            Self::MsgRing { .. } => None,
+        }
+    }
+
+    /// Triggers the notifier.
+    #[inline]
+    pub fn notify(&self) {
+        self.notify_inner(true);
+    }
+
+    /// Triggers the notifier from an interrupt or POSIX signal handler.
+    ///
+    /// Backends guarded by a lock are skipped if the lock is already held, so
+    /// this never deadlocks when the handler interrupts [`Notifier::notify`].
+    #[inline]
+    pub fn notify_from_interrupt(&self) {
+        self.notify_inner(false);
+    }
+
+    #[inline]
+    fn notify_inner(&self, may_block: bool) {
+        let _ = may_block;
+        match *self {
+            #[cfg(all(feature = "eventfd", any(target_os = "linux", target_os = "android")))]
+            Self::EventFd(ref fd) => {
+                let one: u64 = 1;
+                // only fails with EAGAIN once the eventfd counter saturates,
+                // in which case it is readable anyway.
+                unsafe { libc::write(fd.as_raw_fd(), (&raw const one).cast(), 8) };
+            }
+            #[cfg(all(
+                feature = "fd",
+                unix,
+                not(any(target_os = "linux", target_os = "android"))
+            ))]
+            Self::Pipe { ref write, .. } => {
+                let one: u8 = 1;
+                // only fails with EAGAIN once the pipe is full, in which case
+                // it is readable anyway.
+                unsafe { libc::write(write.as_raw_fd(), (&raw const one).cast(), 1) };
+            }
+            #[cfg(all(feature = "io-uring", target_os = "linux"))]
+            Self::MsgRing {
+                ref ring,
+                target,
+                user_data,
+            } => {
+                let mut ring = match ring.try_lock() {
+                    Ok(ring) => ring,
+                    Err(std::sync::TryLockError::Poisoned(e)) => e.into_inner(),
+                    Err(std::sync::TryLockError::WouldBlock) if may_block => ring
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner),
+                    Err(std::sync::TryLockError::WouldBlock) => return,
+                };
+                let sqe = opcode::MsgRingData::new(types::Fd(target), 0, user_data, None)
+                    .build()
+                    .flags(squeue::Flags::SKIP_SUCCESS);
+                // SAFETY: `MSG_RING` references no user memory.
+                if unsafe { ring.submission().push(&sqe) }.is_ok() {
+                    let _ = ring.submit();
+                }
+                // only failed submissions post a completion locally; discard them.
+                ring.completion().for_each(drop);
+            }
+        }
+    }
+}
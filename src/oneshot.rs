@@ -0,0 +1,118 @@
+//! A single-use channel living on the stack.
+//!
+//! [`oneshot_scoped`] hands out a [`OneshotSender`]/[`OneshotReceiver`] pair
+//! that borrows a caller-owned [`OneshotState`] instead of sharing an `Arc`,
+//! so it allocates nothing and can carry non-`'static` values between
+//! threads of a [`std::thread::scope`].
+//!
+//! # Example
+//!
+//! ```
+//! let name = String::from("waitx");
+//! let mut state = waitx::OneshotState::new();
+//! let (tx, rx) = waitx::oneshot_scoped(&mut state);
+//!
+//! std::thread::scope(|s| {
+//!     s.spawn(|| tx.send(name.as_str()));
+//!     assert_eq!(rx.recv(), "waitx");
+//! });
+//! ```
+
+use crate::prelude::*;
+
+/// Storage for one value sent through [`oneshot_scoped`].
+pub struct OneshotState<T> {
+    slot: Slot<T>,
+    sent: Event,
+}
+
+impl<T> OneshotState<T> {
+    /// Creates an empty state.
+    pub fn new() -> Self {
+        Self {
+            slot: Slot::default(),
+            sent: Event::new(),
+        }
+    }
+}
+
+impl<T> Default for OneshotState<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sending half of a [`oneshot_scoped`] channel.
+pub struct OneshotSender<'a, T> {
+    state: &'a OneshotState<T>,
+}
+
+impl<T> OneshotSender<'_, T> {
+    /// Sends `value`, without blocking.
+    #[inline]
+    pub fn send(self, value: T) {
+        // SAFETY: the receiver doesn't read the slot before `sent` is
+        // signaled, and this is the only sender.
+        unsafe {
+            self.state.slot.write(value);
+        }
+        self.state.slot.mark_full();
+        self.state.sent.signal();
+    }
+}
+
+/// Receiving half of a [`oneshot_scoped`] channel.
+pub struct OneshotReceiver<'a, T> {
+    state: &'a OneshotState<T>,
+    wait: WaitState,
+}
+
+impl<T> OneshotReceiver<'_, T> {
+    /// Blocks until the value is sent and returns it.
+    ///
+    /// Blocks forever if the sender is dropped without sending.
+    #[inline]
+    pub fn recv(self) -> T {
+        self.wait.wait(&self.state.sent);
+        self.take()
+    }
+
+    /// Returns the value if it has been sent, without blocking.
+    ///
+    /// Returns `None` again once the value has been taken.
+    #[inline]
+    pub fn try_recv(&mut self) -> Option<T> {
+        if !self.wait.try_wait(&self.state.sent) {
+            return None;
+        }
+        Some(self.take())
+    }
+
+    #[inline(always)]
+    fn take(&self) -> T {
+        let slot = &self.state.slot;
+        slot.mark_empty();
+        // SAFETY: the sender filled the slot before signaling, and won't
+        // touch it again.
+        unsafe { slot.read() }
+    }
+}
+
+/// Creates a single-use channel whose endpoints borrow `state`.
+///
+/// `state` is reset first, dropping a value left over from a previous use.
+/// Waiting uses the calling thread's tuning, see
+/// [`set_thread_tuning`](crate::set_thread_tuning).
+pub fn oneshot_scoped<T>(
+    state: &mut OneshotState<T>,
+) -> (OneshotSender<'_, T>, OneshotReceiver<'_, T>) {
+    *state = OneshotState::new();
+    let state = &*state;
+    (
+        OneshotSender { state },
+        OneshotReceiver {
+            state,
+            wait: WaitState::new(TuningSource::Thread),
+        },
+    )
+}
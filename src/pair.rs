@@ -16,162 +16,1340 @@
 //! waiter.wait(); // blocks until signaled
 //! ```
 
+use crate::fallible::{AllocError, TryArc};
+use crate::notify::Notifier;
 use crate::prelude::*;
 
+use core::cell::Cell;
+use core::ops::Deref;
+
+#[cfg(all(feature = "eventfd", any(target_os = "linux", target_os = "android")))]
+use std::os::fd::{AsFd, BorrowedFd};
+
+#[cfg(all(feature = "fd", unix))]
+use std::os::fd::RawFd;
+
+#[cfg(all(feature = "std", not(feature = "loom")))]
+use std::time::Duration;
+
+/// The state a [`Waker`] shares with its [`Waiter`]: the event counter and
+/// what's needed to wake a blocked waiter.
+///
+/// Pairs normally keep it in an `Arc`; create one with [`Event::new`] to
+/// store it elsewhere, see [`pair_in`].
 #[cfg(feature = "loom")]
-struct Inner {
-    counter: Mutex<u64>,
+pub struct Event {
+    counter: AtomicCount,
+    wake: AtomicU32,
+    state: AtomicU32,
+    /// Stands in for the futex on `wake`, which loom can't model.
+    futex: Mutex<()>,
     condvar: Condvar,
+    notifier: Option<Notifier>,
 }
 
+/// Waiter state: not waiting.
+const IDLE: u32 = 0;
+
+/// Waiter state: waiting, but not parked, so it notices a signal by itself.
+const SPINNING: u32 = 1;
+
+/// Waiter state: parked (or about to), so a signal must wake it.
+const PARKED: u32 = 2;
+
+/// Orders a handshake store before the load that follows it: loom only
+/// models `SeqCst` accesses as `AcqRel`, so the model needs the fence.
+#[inline(always)]
+fn handshake_fence() {
+    #[cfg(feature = "loom")]
+    loom::sync::atomic::fence(Ordering::SeqCst);
+}
+
+/// The state a [`Waker`] shares with its [`Waiter`]: the event counter and
+/// what's needed to wake a blocked waiter.
+///
+/// Pairs normally keep it in an `Arc`; create one with [`Event::new`] to
+/// store it elsewhere, see [`pair_in`].
 #[cfg(not(feature = "loom"))]
-struct Inner {
-    counter: AtomicU64,
+pub struct Event {
+    /// Bumped by wakers, polled by the spinning waiter.
+    counter: CachePadded<AtomicCount>,
     wake: AtomicU32,
-    waiting: AtomicBool,
+    /// The waiter's [`IDLE`], [`SPINNING`] or [`PARKED`] state, stored by
+    /// it around every blocking wait; kept off the counter's line so those
+    /// stores don't stall its polls.
+    state: CachePadded<AtomicU32>,
+    notifier: Option<Notifier>,
+    #[cfg(all(feature = "qos", target_vendor = "apple"))]
+    qos: crate::qos::QosOverride,
+    #[cfg(all(feature = "priority-boost", windows))]
+    priority: crate::priority::PriorityBoost,
+    #[cfg(feature = "std")]
+    stamp: SignalStamp,
 }
 
-#[cfg(not(feature = "loom"))]
-struct WaitingGuard<'a>(&'a AtomicBool);
+impl Event {
+    /// Creates the state of a new pair, with no pending notifications.
+    #[cfg(not(feature = "loom"))]
+    pub const fn new() -> Self {
+        Self::with(0, None)
+    }
+
+    /// Creates the state of a new pair, with no pending notifications.
+    #[cfg(feature = "loom")]
+    pub fn new() -> Self {
+        Self::with(0, None)
+    }
+
+    /// Creates the state of a new pair whose first `count` waits return
+    /// right away; see [`pair_with_initial`].
+    #[cfg(not(feature = "loom"))]
+    #[allow(clippy::unnecessary_cast)]
+    pub const fn with_initial(count: u32) -> Self {
+        Self::with(count as Count, None)
+    }
+
+    /// Creates the state of a new pair whose first `count` waits return
+    /// right away; see [`pair_with_initial`].
+    #[cfg(feature = "loom")]
+    pub fn with_initial(count: u32) -> Self {
+        Self::with(Count::from(count), None)
+    }
+
+    #[cfg(not(feature = "loom"))]
+    pub(crate) const fn with(count: Count, notifier: Option<Notifier>) -> Self {
+        Self {
+            counter: CachePadded::new(AtomicCount::new(count)),
+            wake: AtomicU32::new(0),
+            state: CachePadded::new(AtomicU32::new(IDLE)),
+            notifier,
+            #[cfg(all(feature = "qos", target_vendor = "apple"))]
+            qos: crate::qos::QosOverride::new(),
+            #[cfg(all(feature = "priority-boost", windows))]
+            priority: crate::priority::PriorityBoost::new(),
+            #[cfg(feature = "std")]
+            stamp: SignalStamp::new(),
+        }
+    }
+
+    #[cfg(feature = "loom")]
+    pub(crate) fn with(count: Count, notifier: Option<Notifier>) -> Self {
+        Self {
+            counter: AtomicCount::new(count),
+            wake: AtomicU32::new(0),
+            state: AtomicU32::new(IDLE),
+            futex: Mutex::new(()),
+            condvar: Condvar::new(),
+            notifier,
+        }
+    }
+
+    /// Returns the event to its initial state, discarding pending
+    /// notifications, so it can be reused for another round of
+    /// [`pair_in`] without reallocating.
+    ///
+    /// Taking `&mut self` guarantees that no endpoint still uses it, so no
+    /// thread can be waiting. An attached notifier is kept.
+    pub fn reset(&mut self) {
+        self.counter.store(0, Ordering::Relaxed);
+        self.wake.store(0, Ordering::Relaxed);
+        self.state.store(IDLE, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub(crate) fn signal(&self) {
+        self.signal_n(1);
+    }
+
+    #[inline(always)]
+    pub(crate) fn signal_n(&self, n: Count) {
+        #[cfg(not(feature = "loom"))]
+        {
+            #[cfg(feature = "std")]
+            self.stamp.stamp();
+            self.counter.fetch_add(n, Ordering::Release);
+            self.wake.fetch_add(1, Ordering::SeqCst);
+            crate::arch::notify();
+            #[cfg(all(feature = "qos", target_vendor = "apple"))]
+            self.qos.boost();
+            #[cfg(all(feature = "priority-boost", windows))]
+            self.priority.boost();
+            if self.parked() {
+                crate::park::wake_one(&self.wake);
+            }
+        }
+
+        #[cfg(feature = "loom")]
+        self.bump(n);
+
+        if let Some(notifier) = &self.notifier {
+            notifier.notify();
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn signal_from_isr(&self) {
+        #[cfg(not(feature = "loom"))]
+        {
+            self.counter.fetch_add(1, Ordering::Release);
+            self.wake.fetch_add(1, Ordering::SeqCst);
+            crate::arch::notify();
+            if self.parked() {
+                crate::park::wake_from_isr(&self.wake);
+            }
+            if let Some(notifier) = &self.notifier {
+                notifier.notify_from_interrupt();
+            }
+        }
+
+        #[cfg(feature = "loom")]
+        self.signal();
+    }
+
+    #[cfg(all(feature = "std", unix))]
+    #[inline]
+    pub(crate) fn signal_from_signal_handler(&self) {
+        let _errno = crate::signal::ErrnoGuard::new();
+
+        #[cfg(not(feature = "loom"))]
+        {
+            self.counter.fetch_add(1, Ordering::Release);
+            self.wake.fetch_add(1, Ordering::SeqCst);
+            crate::arch::notify();
+            if self.parked() {
+                crate::park::wake_from_isr(&self.wake);
+            }
+        }
+
+        #[cfg(feature = "loom")]
+        self.bump(1);
+
+        if let Some(notifier) = &self.notifier {
+            notifier.notify_from_interrupt();
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn poke(&self) {
+        if self.is_waiting() {
+            self.signal();
+        }
+    }
+
+    /// Returns whether the waiter is in a blocking wait, spinning or parked.
+    #[inline]
+    pub(crate) fn is_waiting(&self) -> bool {
+        handshake_fence();
+        self.state.load(Ordering::SeqCst) != IDLE
+    }
+
+    /// Returns whether the waiter is parked, so that a signal costs a wake.
+    #[inline]
+    pub(crate) fn is_parked(&self) -> bool {
+        self.parked()
+    }
+
+    /// Returns the event counter: how many signals were sent so far.
+    #[inline]
+    pub(crate) fn sent(&self) -> Count {
+        self.counter.load(Ordering::Relaxed)
+    }
+
+    /// Returns whether the waiter has to be woken, after the wake word was
+    /// bumped.
+    ///
+    /// Pairs with [`Event::prepare_park`]: of the waker's bump and load and
+    /// the waiter's store and load, all `SeqCst`, one side always sees the
+    /// other's store, so either the waker wakes it or it doesn't park.
+    #[inline(always)]
+    fn parked(&self) -> bool {
+        handshake_fence();
+        self.state.load(Ordering::SeqCst) == PARKED
+    }
+
+    /// Marks the waiter as parked; returns whether it may park on `wake`
+    /// holding `val`, i.e. no signal arrived meanwhile.
+    #[inline]
+    fn prepare_park(&self, val: u32) -> bool {
+        self.state.store(PARKED, Ordering::SeqCst);
+        handshake_fence();
+        self.wake.load(Ordering::SeqCst) == val
+    }
+
+    /// Runs `park` marked as parked, returning to spinning afterwards.
+    #[inline]
+    fn park(&self, val: u32, park: impl FnOnce()) {
+        if self.prepare_park(val) {
+            park();
+        }
+        self.state.store(SPINNING, Ordering::Relaxed);
+    }
+
+    /// Bumps the counter by `n` and wakes the waiter if parked, as
+    /// [`Event::signal_n`] does off the model.
+    #[cfg(feature = "loom")]
+    fn bump(&self, n: Count) {
+        self.counter.fetch_add(n, Ordering::Release);
+        self.wake.fetch_add(1, Ordering::SeqCst);
+        if self.parked() {
+            drop(self.futex.lock().unwrap());
+            self.condvar.notify_one();
+        }
+    }
+
+    /// Parks until `done`, right away: loom can't model spinning.
+    #[cfg(feature = "loom")]
+    fn park_until(&self, done: impl Fn() -> bool) {
+        loop {
+            let val = self.wake.load(Ordering::Acquire);
+            if done() {
+                return;
+            }
+            self.park(val, || {
+                let futex = self.futex.lock().unwrap();
+                if self.wake.load(Ordering::SeqCst) == val {
+                    drop(self.condvar.wait(futex).unwrap());
+                }
+            });
+        }
+    }
+}
+
+/// Marks the waiter as [`SPINNING`] until dropped.
+struct WaitingGuard<'a>(&'a AtomicU32);
 
-#[cfg(not(feature = "loom"))]
 impl<'a> WaitingGuard<'a> {
     #[inline(always)]
-    fn new(flag: &'a AtomicBool) -> Self {
-        flag.store(true, Ordering::Release);
-        Self(flag)
+    fn new(state: &'a AtomicU32) -> Self {
+        state.store(SPINNING, Ordering::SeqCst);
+        handshake_fence();
+        Self(state)
     }
 }
 
-#[cfg(not(feature = "loom"))]
 impl Drop for WaitingGuard<'_> {
     #[inline(always)]
     fn drop(&mut self) {
-        self.0.store(false, Ordering::Release);
+        self.0.store(IDLE, Ordering::Release);
+    }
+}
+
+impl Default for Event {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 /// Sends counted notifications to a paired [`Waiter`].
+///
+/// `S` is how it holds the shared [`Event`]: an `Arc` unless the pair was
+/// created with [`pair_in`].
 #[derive(Clone)]
-pub struct Waker {
-    inner: Arc<Inner>,
+pub struct Waker<S = Arc<Event>> {
+    inner: S,
 }
 
-impl Waker {
+impl<S: Deref<Target = Event>> Waker<S> {
     /// Increments the event counter and wakes the waiting thread.
+    ///
+    /// Takes no lock the waiter can hold: the counter and wake word are
+    /// updated atomically and the wake is a single futex-style syscall, so a
+    /// high-priority signaler never waits on a low-priority waiter
+    /// (`SCHED_FIFO` safe). The one exception is a `pair_with_msg_ring`
+    /// notifier, whose submission ring is locked among wakers only.
     #[inline(always)]
     pub fn signal(&self) {
-        #[cfg(not(feature = "loom"))]
-        {
-            self.inner.counter.fetch_add(1, Ordering::Release);
-            self.inner.wake.fetch_add(1, Ordering::Release);
-            crate::atomic_wait::wake_one(&self.inner.wake);
-        }
+        self.inner.signal();
+    }
 
-        #[cfg(feature = "loom")]
-        {
-            *self.inner.counter.lock().unwrap() += 1;
-            self.inner.condvar.notify_one();
+    /// Increments the event counter by `n` at once, waking the waiting thread
+    /// at most once.
+    ///
+    /// Equivalent to `n` calls to [`signal`](Self::signal), for producers
+    /// that generate several events at a time; pairs with
+    /// [`Waiter::wait_many`]. Does nothing if `n` is zero.
+    #[inline]
+    pub fn signal_n(&self, n: u32) {
+        if n != 0 {
+            self.inner.signal_n(Count::from(n));
         }
     }
 
+    /// Increments the event counter and wakes the waiting thread from an
+    /// interrupt handler.
+    ///
+    /// Performs no locking or allocation: just atomic increments followed by
+    /// [`ParkHooks::wake_from_isr`](crate::ParkHooks::wake_from_isr) (or the
+    /// native wake without hooks installed). An attached eventfd or pipe is
+    /// notified as by [`signal`](Self::signal); an io_uring notifier is
+    /// skipped if the interrupt hit a submission on the same pair.
+    #[inline(always)]
+    pub fn signal_from_isr(&self) {
+        self.inner.signal_from_isr();
+    }
+
+    /// Increments the event counter and wakes the waiting thread from a POSIX
+    /// signal handler.
+    ///
+    /// Async-signal-safe: takes no locks and doesn't allocate, wakes through
+    /// the same path as [`Waker::signal_from_isr`], and preserves `errno`.
+    /// An attached io_uring notifier is skipped if the handler interrupted a
+    /// submission on the same pair.
+    #[cfg(all(feature = "std", unix))]
+    #[inline]
+    pub fn signal_from_signal_handler(&self) {
+        self.inner.signal_from_signal_handler();
+    }
+
     /// Wakes the waiter only if it is currently blocked.
+    ///
+    /// A waiter counts as blocked from the moment a wait starts, before it
+    /// checks for a pending notification, so a poke issued once a wait has
+    /// found nothing pending is never lost. A poke issued while no wait is
+    /// in progress is dropped, not saved for the next wait; use
+    /// [`Waker::signal`] for that.
     #[inline(always)]
     pub fn poke(&self) {
-        #[cfg(not(feature = "loom"))]
-        {
-            if self.inner.waiting.load(Ordering::Acquire) {
-                self.signal();
-            }
+        self.inner.poke();
+    }
+
+    /// Returns whether the waiter is parked right now, i.e. blocked in the
+    /// OS rather than spinning, so the next signal has to wake it.
+    ///
+    /// A snapshot that may be stale by the time it is acted on: use it to
+    /// decide between handing work off now and batching a little longer,
+    /// not for correctness.
+    #[inline]
+    pub fn waiter_is_parked(&self) -> bool {
+        self.inner.is_parked()
+    }
+
+    /// Returns whether `waiter` is the other half of this pair.
+    #[inline]
+    pub fn same_pair(&self, waiter: &Waiter<S>) -> bool {
+        core::ptr::eq(&*self.inner, &*waiter.inner)
+    }
+
+    /// Returns how many signals were sent through this pair so far, by any
+    /// waker.
+    ///
+    /// The count wraps around (at `u32::MAX` on targets without 64-bit
+    /// atomics); compare readings by wrapping difference.
+    #[inline]
+    pub fn signals_sent(&self) -> u64 {
+        #[allow(clippy::useless_conversion)]
+        u64::from(self.inner.sent())
+    }
+
+    /// Starts collecting signals locally instead of publishing each one.
+    ///
+    /// Signals sent through the returned [`Deferred`] reach the waiter in a
+    /// single [`signal_n`](Self::signal_n) on [`Deferred::flush`] or when it
+    /// is dropped, so a producer publishing several items under a lock pays
+    /// for one wake instead of one per item.
+    #[inline]
+    pub fn defer(&self) -> Deferred<'_, S> {
+        Deferred {
+            waker: self,
+            pending: Cell::new(0),
         }
+    }
+}
 
-        #[cfg(feature = "loom")]
-        self.signal();
+/// Signals collected by [`Waker::defer`], published together on
+/// [`flush`](Self::flush) or drop.
+pub struct Deferred<'a, S: Deref<Target = Event> = Arc<Event>> {
+    waker: &'a Waker<S>,
+    pending: Cell<u32>,
+}
+
+impl<S: Deref<Target = Event>> Deferred<'_, S> {
+    /// Records a signal, without waking the waiter yet.
+    ///
+    /// Flushes on its own once `u32::MAX` signals are pending.
+    #[inline(always)]
+    pub fn signal(&self) {
+        let pending = self.pending.get() + 1;
+        self.pending.set(pending);
+        if pending == u32::MAX {
+            self.flush();
+        }
+    }
+
+    /// Returns how many signals are waiting for the next flush.
+    #[inline(always)]
+    pub fn pending(&self) -> u32 {
+        self.pending.get()
+    }
+
+    /// Publishes the signals recorded so far with a single wake.
+    #[inline]
+    pub fn flush(&self) {
+        self.waker.signal_n(self.pending.take());
     }
 }
 
-/// A counted, blocking notification primitive.
-pub struct Waiter {
-    inner: Arc<Inner>,
-    next: AtomicU64,
+impl<S: Deref<Target = Event>> Drop for Deferred<'_, S> {
+    fn drop(&mut self) {
+        self.flush();
+    }
 }
 
-impl Waiter {
-    /// Blocks until the next notification, using provided tuning.
+/// Where [`Waiter::wait`] takes its tuning from.
+#[derive(Clone)]
+pub(crate) enum TuningSource {
+    Thread,
+    Fixed(Tuning),
+    #[cfg(feature = "std")]
+    Shared(CachedTuning),
+}
+
+impl TuningSource {
+    /// Follows `tuning`, keeping a copy that is refreshed when it changes.
+    #[cfg(feature = "std")]
+    pub(crate) fn shared(tuning: &SharedTuning) -> Self {
+        Self::Shared(CachedTuning::new(tuning.clone()))
+    }
+
+    #[inline(always)]
+    fn get(&self) -> Tuning {
+        match self {
+            Self::Thread => thread_tuning(),
+            Self::Fixed(tuning) => *tuning,
+            #[cfg(feature = "std")]
+            Self::Shared(shared) => shared.get(),
+        }
+    }
+}
+
+/// The waiting side's own state: how many notifications it consumed, and
+/// how it waits for the next.
+pub(crate) struct WaitState {
+    /// Notifications consumed so far. Only the waiting side touches it, so
+    /// it lives inline here rather than in the shared allocation.
+    next: AtomicCount,
+    tuning: TuningSource,
+    /// Most notifications kept pending, older ones being dropped; see
+    /// [`pair_with_max_pending`].
+    max_pending: Option<Count>,
+    #[cfg(not(feature = "loom"))]
+    history: Adaptive,
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    watchdog: Option<Watchdog>,
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    sla: Option<LatencySla>,
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    predictor: Predictor,
+}
+
+impl WaitState {
+    #[cfg(not(feature = "loom"))]
+    pub(crate) const fn new(tuning: TuningSource) -> Self {
+        Self {
+            next: AtomicCount::new(0),
+            tuning,
+            max_pending: None,
+            history: Adaptive::new(),
+            #[cfg(feature = "std")]
+            watchdog: None,
+            #[cfg(feature = "std")]
+            sla: None,
+            #[cfg(feature = "std")]
+            predictor: Predictor::new(),
+        }
+    }
+
+    #[cfg(feature = "loom")]
+    pub(crate) fn new(tuning: TuningSource) -> Self {
+        Self {
+            next: Default::default(),
+            tuning,
+            max_pending: None,
+        }
+    }
+
     #[inline]
-    pub fn wait_with(&self, tuning: Tuning) {
-        let target = self.next.fetch_add(1, Ordering::Relaxed) + 1;
+    pub(crate) fn wait_with(&self, event: &Event, tuning: Tuning) {
+        self.wait_n(event, tuning, 1);
+    }
+
+    /// Blocks until `n` more notifications arrived, consuming them.
+    #[inline]
+    pub(crate) fn wait_n(&self, event: &Event, tuning: Tuning, n: Count) {
+        self.collapse(event);
+        let target = self.next.fetch_add(n, Ordering::Relaxed).wrapping_add(n);
+        self.wait_for(event, tuning, target);
+    }
+
+    /// Blocks until the event counter reached `target`, consuming nothing.
+    #[inline]
+    pub(crate) fn wait_for(&self, event: &Event, tuning: Tuning, target: Count) {
+        self.wait_for_unless(event, tuning, target, || false);
+    }
+
+    /// Blocks until the next notification, consuming it, or until `stop`
+    /// returns `true`, which a wake without a signal (`signal_n(0)`) makes
+    /// the waiter check. Returns whether a notification was consumed.
+    #[inline]
+    pub(crate) fn wait_unless(
+        &self,
+        event: &Event,
+        tuning: Tuning,
+        stop: impl Fn() -> bool,
+    ) -> bool {
+        self.collapse(event);
+        let target = self.next.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+        if self.wait_for_unless(event, tuning, target, stop) {
+            return true;
+        }
+        // give the notification back, as on a timeout
+        self.next.fetch_sub(1, Ordering::Relaxed);
+        false
+    }
+
+    /// Blocks until the event counter reached `target` or `stop` returns
+    /// `true`, consuming nothing; returns whether the target was reached.
+    #[inline(always)]
+    fn wait_for_unless(
+        &self,
+        event: &Event,
+        tuning: Tuning,
+        target: Count,
+        stop: impl Fn() -> bool,
+    ) -> bool {
+        // published before the first check, so a poke that finds the waiter
+        // idle comes before it and is seen by the check
+        let _wg = WaitingGuard::new(&event.state);
+        let arrived = || reached(event.counter.load(Ordering::Acquire), target);
+        if arrived() {
+            return true;
+        }
+        if stop() {
+            // the event may have been signaled right before the stop
+            return arrived();
+        }
+        let done = || arrived() || stop();
 
         #[cfg(not(feature = "loom"))]
         {
-            if self.inner.counter.load(Ordering::Acquire) >= target {
-                return;
+            #[cfg(feature = "std")]
+            match &self.watchdog {
+                Some(watchdog) => self.wait_until(event, done, tuning, watchdog.park()),
+                None => self.wait_until(event, done, tuning, crate::park::wait),
+            }
+            #[cfg(not(feature = "std"))]
+            self.wait_until(event, done, tuning, crate::park::wait);
+            if !arrived() {
+                return false;
             }
-            let _wg = WaitingGuard::new(&self.inner.waiting);
-            wait_until_with_tuning(
-                || self.inner.counter.load(Ordering::Acquire) >= target,
-                &self.inner.wake,
-                tuning,
-            );
+            #[cfg(feature = "std")]
+            self.check_sla(event);
+            true
         }
 
         #[cfg(feature = "loom")]
         {
             let _ = tuning;
-            let mut guard = self.inner.counter.lock().unwrap();
-            while *guard < target {
-                guard = self.inner.condvar.wait(guard).unwrap();
-            }
+            event.park_until(done);
+            arrived()
         }
     }
 
-    /// Blocks until the next notification using default tuning.
+    /// Drops pending notifications beyond `max_pending`, keeping the most
+    /// recent ones.
     #[inline(always)]
-    pub fn wait(&self) {
-        self.wait_with(Tuning::DEFAULT);
+    fn collapse(&self, event: &Event) {
+        let Some(max) = self.max_pending else {
+            return;
+        };
+
+        let counter = event.counter.load(Ordering::Relaxed);
+
+        // only the waiting side advances `next`, so this can't race
+        let pending = counter.wrapping_sub(self.next.load(Ordering::Relaxed));
+        if pending > max && pending <= Count::MAX / 2 {
+            self.next
+                .store(counter.wrapping_sub(max), Ordering::Relaxed);
+        }
     }
 
-    /// Attempts to consume a notification without blocking.
+    #[inline(always)]
+    pub(crate) fn wait(&self, event: &Event) {
+        self.wait_with(event, self.tuning());
+    }
+
+    /// The tuning [`wait`](Self::wait) uses.
+    #[inline(always)]
+    pub(crate) fn tuning(&self) -> Tuning {
+        self.tuning.get()
+    }
+
+    /// Spins, then parks, until `done`; the caller holds a [`WaitingGuard`]
+    /// from before its own first check.
+    #[cfg(not(feature = "loom"))]
+    #[inline(always)]
+    fn wait_until(
+        &self,
+        event: &Event,
+        mut done: impl FnMut() -> bool,
+        tuning: Tuning,
+        park: impl Fn(&AtomicU32, u32),
+    ) {
+        #[cfg(all(feature = "qos", target_vendor = "apple"))]
+        let _qos = event.qos.enter();
+        #[cfg(all(feature = "priority-boost", windows))]
+        let _priority = event.priority.enter();
+        #[cfg(feature = "std")]
+        if tuning.predictive {
+            self.predictor
+                .pre_arm(&mut done, &event.wake, |wake, val, timeout| {
+                    event.park(val, || crate::park::wait_timeout(wake, val, timeout))
+                });
+        }
+        let park = |wake: &AtomicU32, val| event.park(val, || park(wake, val));
+        if tuning.adaptive || tuning.burst {
+            wait_until_adaptive(&mut done, &event.wake, tuning, &self.history, park);
+        } else {
+            wait_until_with_park(&mut done, &event.wake, tuning, park);
+        }
+        #[cfg(feature = "std")]
+        if tuning.predictive {
+            self.predictor.record();
+        }
+    }
+
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    pub(crate) fn wait_timeout_with(
+        &self,
+        event: &Event,
+        tuning: Tuning,
+        timeout: Duration,
+    ) -> bool {
+        self.collapse(event);
+        let target = self.next.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+        let _wg = WaitingGuard::new(&event.state);
+        let done = || reached(event.counter.load(Ordering::Acquire), target);
+        if done() {
+            return true;
+        }
+
+        let deadline = Deadline::new(timeout);
+        self.wait_until(
+            event,
+            || done() || deadline.poll(),
+            deadline.budget(tuning),
+            |wake, val| deadline.park(wake, val),
+        );
+        if done() {
+            self.check_sla(event);
+            return true;
+        }
+        // give the notification back; one arriving meanwhile is kept for the
+        // next wait
+        self.next.fetch_sub(1, Ordering::Relaxed);
+        false
+    }
+
+    #[cfg(all(feature = "std", not(feature = "loom")))]
     #[inline]
-    pub fn try_wait(&self) -> bool {
-        let target = self.next.load(Ordering::Relaxed) + 1;
+    pub(crate) fn wait_timeout(&self, event: &Event, timeout: Duration) -> bool {
+        self.wait_timeout_with(event, self.tuning.get(), timeout)
+    }
 
-        #[cfg(not(feature = "loom"))]
-        let ready = self.inner.counter.load(Ordering::Acquire) >= target;
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    #[inline(always)]
+    fn check_sla(&self, event: &Event) {
+        if let Some(sla) = &self.sla {
+            sla.check(&event.stamp);
+        }
+    }
 
-        #[cfg(feature = "loom")]
-        let ready = *self.inner.counter.lock().unwrap() >= target;
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    pub(crate) fn set_latency_sla(
+        &mut self,
+        event: &Event,
+        limit: Duration,
+        on_breach: impl Fn(Duration) + Send + Sync + 'static,
+    ) {
+        self.sla = Some(LatencySla::new(limit, Box::new(on_breach)));
+        event.stamp.enable();
+    }
+
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    pub(crate) fn sla_breaches(&self) -> u64 {
+        self.sla.as_ref().map_or(0, LatencySla::breaches)
+    }
 
-        if ready {
+    #[inline]
+    pub(crate) fn try_wait(&self, event: &Event) -> bool {
+        self.collapse(event);
+        if self.ready(event) {
             self.next.fetch_add(1, Ordering::Relaxed);
             true
         } else {
             false
         }
     }
+
+    /// Creates the state of a waiting side taking over from one that
+    /// consumed `consumed` notifications of the same event.
+    #[cfg(not(feature = "loom"))]
+    pub(crate) fn resume(tuning: TuningSource, consumed: Count) -> Self {
+        let state = Self::new(tuning);
+        state.next.store(consumed, Ordering::Relaxed);
+        state
+    }
+
+    /// Where [`wait`](Self::wait) takes its tuning from.
+    #[cfg(not(feature = "loom"))]
+    pub(crate) fn tuning_source(&self) -> TuningSource {
+        self.tuning.clone()
+    }
+
+    /// Returns how many notifications were consumed so far.
+    #[inline]
+    pub(crate) fn consumed(&self) -> Count {
+        self.next.load(Ordering::Relaxed)
+    }
+
+    /// Consumes every pending notification, returning how many there were.
+    #[inline]
+    pub(crate) fn drain(&self, event: &Event) -> Count {
+        self.collapse(event);
+        let pending = self.pending(event);
+        self.next.fetch_add(pending, Ordering::Relaxed);
+        pending
+    }
+
+    /// Consumes up to `n` pending notifications, returning how many.
+    #[inline]
+    pub(crate) fn skip(&self, event: &Event, n: Count) -> Count {
+        self.collapse(event);
+        let skipped = self.pending(event).min(n);
+        self.next.fetch_add(skipped, Ordering::Relaxed);
+        skipped
+    }
+
+    /// Returns how many notifications are pending, up to `max_pending`.
+    #[inline]
+    pub(crate) fn pending(&self, event: &Event) -> Count {
+        let counter = event.counter.load(Ordering::Acquire);
+
+        let pending = counter.wrapping_sub(self.next.load(Ordering::Relaxed));
+        if pending > Count::MAX / 2 {
+            // only a wait in progress runs ahead of the counter
+            return 0;
+        }
+        self.max_pending.map_or(pending, |max| pending.min(max))
+    }
+
+    /// Returns whether a notification is pending, without consuming it.
+    #[inline]
+    pub(crate) fn ready(&self, event: &Event) -> bool {
+        let target = self.next.load(Ordering::Relaxed).wrapping_add(1);
+
+        reached(event.counter.load(Ordering::Acquire), target)
+    }
+}
+
+/// A counted, blocking notification primitive.
+///
+/// `S` is how it holds the shared [`Event`], as for [`Waker`].
+pub struct Waiter<S = Arc<Event>> {
+    inner: S,
+    state: WaitState,
+}
+
+impl<S: Deref<Target = Event>> Waiter<S> {
+    /// Blocks until the next notification, using provided tuning.
+    #[inline]
+    pub fn wait_with(&self, tuning: Tuning) {
+        self.state.wait_with(&self.inner, tuning);
+    }
+
+    /// Blocks until the next notification or until `timeout` has elapsed,
+    /// using provided tuning. Returns whether a notification arrived.
+    ///
+    /// The phases are budgeted against the time remaining: time bounds are
+    /// shortened to it, sleeps that would overrun it are skipped, the spin
+    /// phases stop once it has passed, and parking blocks no longer than it.
+    /// A timed-out wait consumes no notification.
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    pub fn wait_timeout_with(&self, tuning: Tuning, timeout: Duration) -> bool {
+        self.state.wait_timeout_with(&self.inner, tuning, timeout)
+    }
+
+    /// Like [`wait_timeout_with`](Self::wait_timeout_with), using the same
+    /// tuning as [`wait`](Self::wait).
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    #[inline]
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        self.state.wait_timeout(&self.inner, timeout)
+    }
+
+    /// Sets the expected wake latency: whenever a blocked wait returns more
+    /// than `limit` after the [`Waker::signal`] that woke it, the breach is
+    /// counted (see [`sla_breaches`](Self::sla_breaches)) and `on_breach`
+    /// is called with the actual latency.
+    ///
+    /// Only waits that had to block are measured, against the latest
+    /// `signal` (not `signal_from_isr` or `signal_from_signal_handler`).
+    /// Once set, each `signal` also reads the clock.
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    pub fn set_latency_sla(
+        &mut self,
+        limit: Duration,
+        on_breach: impl Fn(Duration) + Send + Sync + 'static,
+    ) {
+        self.state.set_latency_sla(&self.inner, limit, on_breach);
+    }
+
+    /// Returns how many wakes exceeded the latency set with
+    /// [`set_latency_sla`](Self::set_latency_sla).
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    pub fn sla_breaches(&self) -> u64 {
+        self.state.sla_breaches()
+    }
+
+    /// Calls `on_stall` whenever a wait has been parked for another `limit`,
+    /// with the total time parked so far, then keeps waiting.
+    ///
+    /// Meant for diagnosing lost or late signals: the callback can log,
+    /// record a metric, or panic. Only the park phase of untimed waits is
+    /// watched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let (waker, mut waiter) = waitx::pair();
+    /// waiter.set_watchdog(Duration::from_secs(1), |parked| {
+    ///     eprintln!("still waiting after {parked:?}");
+    /// });
+    ///
+    /// waker.signal();
+    /// waiter.wait();
+    /// ```
+    #[cfg(all(feature = "std", not(feature = "loom")))]
+    pub fn set_watchdog(
+        &mut self,
+        limit: Duration,
+        on_stall: impl Fn(Duration) + Send + Sync + 'static,
+    ) {
+        self.state.watchdog = Some(Watchdog::new(limit, Box::new(on_stall)));
+    }
+
+    /// Blocks until `n` more notifications arrived, consuming all of them,
+    /// using the same tuning as [`wait`](Self::wait).
+    ///
+    /// Equivalent to `n` calls to [`wait`](Self::wait), but checks the
+    /// counter against the final target only. Returns immediately if `n` is
+    /// zero.
+    #[inline]
+    pub fn wait_many(&self, n: u32) {
+        if n != 0 {
+            self.state
+                .wait_n(&self.inner, self.state.tuning.get(), Count::from(n));
+        }
+    }
+
+    /// Blocks until at least `target` signals were sent in total, as counted
+    /// by [`Waker::signals_sent`], using the same tuning as
+    /// [`wait`](Self::wait).
+    ///
+    /// For sequence-number protocols, e.g. "wake me once item 1000 is
+    /// published". Consumes nothing: the waiter's own position, and so
+    /// [`pending`](Self::pending), stays put. The target is compared by
+    /// wrapping difference, so it must be less than half the counter's
+    /// range ahead.
+    #[inline]
+    #[allow(clippy::unnecessary_cast)]
+    pub fn wait_for_count(&self, target: u64) {
+        self.state
+            .wait_for(&self.inner, self.state.tuning.get(), target as Count);
+    }
+
+    /// Blocks until the next notification, parking right away without
+    /// spinning or yielding first.
+    ///
+    /// For waits known to be long, e.g. for user input.
+    #[inline]
+    pub fn wait_park_only(&self) {
+        self.wait_with(Tuning::new(0, 0));
+    }
+
+    /// Blocks until the next notification, using the waiter's tuning if it
+    /// was created with [`pair_with_tuning`] or `pair_with_shared_tuning`,
+    /// else the thread's ([`thread_tuning`]).
+    #[inline(always)]
+    pub fn wait(&self) {
+        self.state.wait(&self.inner);
+    }
+
+    /// Returns the `eventfd` backing this pair, if it was created with
+    /// [`pair_with_eventfd`].
+    ///
+    /// The descriptor is readable once a signal arrives. To use it from an
+    /// epoll/mio loop, read (and discard) its 8-byte value to re-arm it, then
+    /// consume notifications with [`Waiter::try_wait`] until it returns `false`.
+    #[cfg(all(feature = "eventfd", any(target_os = "linux", target_os = "android")))]
+    pub fn eventfd(&self) -> Option<BorrowedFd<'_>> {
+        match self.inner.notifier.as_ref()? {
+            Notifier::EventFd(fd) => Some(fd.as_fd()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns a descriptor that becomes readable once a signal arrives, if
+    /// the pair was created with [`pair_with_fd`].
+    ///
+    /// It can be handed to `poll`/`select`/`epoll` loops. To re-arm it, read
+    /// from it until the read would block, then consume notifications with
+    /// [`Waiter::try_wait`] until it returns `false`. The descriptor remains
+    /// owned by the pair.
+    #[cfg(all(feature = "fd", unix))]
+    pub fn as_raw_fd(&self) -> Option<RawFd> {
+        self.inner.notifier.as_ref()?.as_raw_fd()
+    }
+
+    /// Returns how many signals have been sent but not yet waited on.
+    ///
+    /// That many waits would return without blocking, so a consumer can
+    /// batch up its work before blocking again.
+    #[inline]
+    pub fn pending(&self) -> u64 {
+        #[allow(clippy::useless_conversion)]
+        u64::from(self.state.pending(&self.inner))
+    }
+
+    /// Returns how many signals this waiter has consumed so far, including
+    /// ones dropped by [`pair_with_max_pending`].
+    ///
+    /// Wraps around like [`Waker::signals_sent`]; their difference is the
+    /// backlog, see also [`pending`](Self::pending).
+    #[inline]
+    pub fn signals_consumed(&self) -> u64 {
+        #[allow(clippy::useless_conversion)]
+        u64::from(self.state.consumed())
+    }
+
+    /// Returns whether `waker` signals this waiter.
+    #[inline]
+    pub fn same_pair(&self, waker: &Waker<S>) -> bool {
+        waker.same_pair(self)
+    }
+
+    /// Returns whether a wait would return right away, without consuming the
+    /// signal.
+    #[inline]
+    pub fn is_signaled(&self) -> bool {
+        self.state.ready(&self.inner)
+    }
+
+    /// Polls for a notification up to `iters` times, pausing with
+    /// [`cpu_relax`] in between, and consumes it if one arrives.
+    ///
+    /// Never yields or parks, so it is bounded like [`try_wait`](Self::try_wait)
+    /// while catching a signal that is just about to arrive.
+    #[inline]
+    pub fn try_wait_spin(&self, iters: u32) -> bool {
+        for _ in 0..iters {
+            if self.try_wait() {
+                return true;
+            }
+            cpu_relax();
+        }
+        self.try_wait()
+    }
+
+    /// Consumes every pending signal without blocking and returns how many
+    /// there were, for consumers that treat several signals as one "go check
+    /// the queue" event.
+    #[inline]
+    pub fn drain(&self) -> u64 {
+        #[allow(clippy::useless_conversion)]
+        u64::from(self.state.drain(&self.inner))
+    }
+
+    /// Discards up to `n` pending signals without blocking and returns how
+    /// many were discarded, e.g. to drop a stale backlog after a reconnect.
+    ///
+    /// Only signals already sent are skipped; later ones are waited for as
+    /// usual.
+    #[inline]
+    #[allow(clippy::useless_conversion, clippy::unnecessary_cast)]
+    pub fn skip(&self, n: u32) -> u32 {
+        // never more than `n`, so this can't truncate
+        self.state.skip(&self.inner, Count::from(n)) as u32
+    }
+
+    /// Attempts to consume a notification without blocking.
+    #[inline]
+    pub fn try_wait(&self) -> bool {
+        self.state.try_wait(&self.inner)
+    }
 }
 
 /// Creates a new counted notification pair.
 pub fn pair() -> (Waker, Waiter) {
-    #[cfg(not(feature = "loom"))]
-    let inner = Arc::new(Inner {
-        counter: Default::default(),
-        wake: Default::default(),
-        waiting: Default::default(),
-    });
+    pair_with_source(TuningSource::Thread, None)
+}
 
-    #[cfg(feature = "loom")]
-    let inner = Arc::new(Inner {
-        counter: Mutex::new(0),
-        condvar: Condvar::new(),
-    });
+/// Creates a new counted notification pair whose [`Waiter::wait`] uses
+/// `tuning`.
+pub fn pair_with_tuning(tuning: Tuning) -> (Waker, Waiter) {
+    pair_with_source(TuningSource::Fixed(tuning), None)
+}
 
+/// Creates a new counted notification pair whose [`Waiter::wait`] uses the
+/// current value of `tuning`; see [`SharedTuning`].
+#[cfg(feature = "std")]
+pub fn pair_with_shared_tuning(tuning: &SharedTuning) -> (Waker, Waiter) {
+    pair_with_source(TuningSource::shared(tuning), None)
+}
+
+/// Creates a new counted notification pair credited with `count` signals,
+/// so its first `count` waits return right away.
+///
+/// The credits count towards [`Waker::signals_sent`]. Suits
+/// resource-counting uses, e.g. a pool of `count` free buffers that the
+/// waker replenishes.
+pub fn pair_with_initial(count: u32) -> (Waker, Waiter) {
+    pair_in(Arc::new(Event::with_initial(count)))
+}
+
+/// Creates a new notification pair whose signals coalesce: any number of
+/// signals sent while the waiter isn't consuming them leave a single pending
+/// notification, like `tokio::sync::Notify`.
+///
+/// Suits "something changed, go look" wake-ups, where a waiter that fell
+/// behind should check once rather than return from [`Waiter::wait`] once per
+/// missed signal.
+pub fn pair_coalescing() -> (Waker, Waiter) {
+    pair_with_max_pending(1)
+}
+
+/// Creates a new counted notification pair that keeps at most `max`
+/// notifications pending, dropping the oldest beyond that.
+///
+/// A waiter that falls behind its wakers then returns from at most `max`
+/// waits without blocking before it catches up, instead of working through
+/// every signal sent while it was stalled. A `max` of 0 is treated as 1; see
+/// also [`pair_coalescing`].
+pub fn pair_with_max_pending(max: u32) -> (Waker, Waiter) {
+    let (waker, mut waiter) = pair();
+    waiter.state.max_pending = Some(Count::from(max.max(1)));
+    (waker, waiter)
+}
+
+/// Creates a new counted notification pair whose signals also increment an
+/// `eventfd`, so they can be multiplexed with other readiness sources.
+///
+/// See [`Waiter::eventfd`].
+#[cfg(all(feature = "eventfd", any(target_os = "linux", target_os = "android")))]
+pub fn pair_with_eventfd() -> std::io::Result<(Waker, Waiter)> {
+    Ok(pair_with_source(
+        TuningSource::Thread,
+        Some(Notifier::eventfd()?),
+    ))
+}
+
+/// Creates a new counted notification pair whose [`Waiter`] exposes a
+/// pollable file descriptor: an `eventfd` on Linux/Android, a self-pipe on
+/// other unix platforms.
+///
+/// See [`Waiter::as_raw_fd`].
+#[cfg(all(feature = "fd", unix))]
+pub fn pair_with_fd() -> std::io::Result<(Waker, Waiter)> {
+    Ok(pair_with_source(
+        TuningSource::Thread,
+        Some(Notifier::pollable()?),
+    ))
+}
+
+/// Creates a new counted notification pair whose signals also post an
+/// `IORING_OP_MSG_RING` completion with `user_data` to the io_uring `ring_fd`.
+///
+/// This lets a thread sleeping in `io_uring_enter` receive waitx
+/// notifications alongside its I/O completions; on such a completion it
+/// consumes them with [`Waiter::try_wait`]. The waker submits from a small
+/// private ring (Linux 5.18+). `ring_fd` must outlive the pair.
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub fn pair_with_msg_ring(
+    ring_fd: std::os::fd::RawFd,
+    user_data: u64,
+) -> std::io::Result<(Waker, Waiter)> {
+    Ok(pair_with_source(
+        TuningSource::Thread,
+        Some(Notifier::msg_ring(ring_fd, user_data)?),
+    ))
+}
+
+/// Creates a counted notification pair over an [`Event`] stored by the
+/// caller, such as a `&'static Event` or one allocated in an arena.
+///
+/// Cloning the waker then clones `event`, so a reference costs no reference
+/// counting. Each `event` must back a single pair, created before any signal.
+///
+/// ```
+/// static EVENT: waitx::Event = waitx::Event::new();
+///
+/// let (waker, waiter) = waitx::pair_in(&EVENT);
+/// std::thread::spawn(move || waker.signal());
+/// waiter.wait();
+/// ```
+pub fn pair_in<S: Deref<Target = Event> + Clone>(event: S) -> (Waker<S>, Waiter<S>) {
+    let waker = Waker {
+        inner: event.clone(),
+    };
+    let waiter = Waiter {
+        inner: event,
+        state: WaitState::new(TuningSource::Thread),
+    };
+    (waker, waiter)
+}
+
+/// A pair whose shared state was allocated by [`try_pair`].
+pub type TryPair = (Waker<TryArc<Event>>, Waiter<TryArc<Event>>);
+
+/// Creates a new counted notification pair like [`pair`], but returns
+/// [`AllocError`] instead of aborting if allocating it fails.
+pub fn try_pair() -> Result<TryPair, AllocError> {
+    Ok(pair_in(TryArc::try_new(Event::new())?))
+}
+
+fn pair_with_source(tuning: TuningSource, notifier: Option<Notifier>) -> (Waker, Waiter) {
+    let inner = Arc::new(Event::with(0, notifier));
     let waker = Waker {
         inner: inner.clone(),
     };
     let waiter = Waiter {
         inner,
-        next: Default::default(),
+        state: WaitState::new(tuning),
     };
     (waker, waiter)
 }
+
+/// A notification pair that can live in a `static`, without `Arc` or lazy
+/// initialization.
+///
+/// ```
+/// static EVENT: waitx::StaticPair = waitx::StaticPair::new();
+///
+/// std::thread::spawn(|| EVENT.waker().signal());
+/// EVENT.waiter().wait();
+/// ```
+///
+/// Any number of [`StaticWaker`]s may signal it, but like a [`Waiter`] it
+/// is meant to be waited on by one thread at a time; debug builds panic if
+/// two threads wait at once. Waits use the calling thread's tuning.
+#[cfg(not(feature = "loom"))]
+pub struct StaticPair {
+    event: Event,
+    state: WaitState,
+    waiting: ThreadTag,
+}
+
+#[cfg(not(feature = "loom"))]
+impl StaticPair {
+    /// Creates a new pair with no pending notifications.
+    pub const fn new() -> Self {
+        Self {
+            event: Event::new(),
+            state: WaitState::new(TuningSource::Thread),
+            waiting: ThreadTag::new(),
+        }
+    }
+
+    /// Returns a handle that signals this pair.
+    #[inline(always)]
+    pub fn waker(&'static self) -> StaticWaker {
+        StaticWaker(self)
+    }
+
+    /// Returns a handle that waits on this pair.
+    #[inline(always)]
+    pub fn waiter(&'static self) -> StaticWaiter {
+        StaticWaiter(self)
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+impl Default for StaticPair {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Signaling handle of a [`StaticPair`]; see [`Waker`].
+#[cfg(not(feature = "loom"))]
+#[derive(Clone, Copy)]
+pub struct StaticWaker(&'static StaticPair);
+
+#[cfg(not(feature = "loom"))]
+impl StaticWaker {
+    /// See [`Waker::signal`].
+    #[inline(always)]
+    pub fn signal(self) {
+        self.0.event.signal();
+    }
+
+    /// See [`Waker::signal_n`].
+    #[inline]
+    pub fn signal_n(self, n: u32) {
+        if n != 0 {
+            self.0.event.signal_n(Count::from(n));
+        }
+    }
+
+    /// See [`Waker::signal_from_isr`].
+    #[inline(always)]
+    pub fn signal_from_isr(self) {
+        self.0.event.signal_from_isr();
+    }
+
+    /// See [`Waker::poke`].
+    #[inline(always)]
+    pub fn poke(self) {
+        self.0.event.poke();
+    }
+}
+
+/// Waiting handle of a [`StaticPair`]; see [`Waiter`].
+#[cfg(not(feature = "loom"))]
+#[derive(Clone, Copy)]
+pub struct StaticWaiter(&'static StaticPair);
+
+#[cfg(not(feature = "loom"))]
+impl StaticWaiter {
+    /// See [`Waiter::wait`].
+    #[inline]
+    pub fn wait(self) {
+        let _use = self.0.waiting.enter("StaticWaiter");
+        self.0.state.wait(&self.0.event);
+    }
+
+    /// See [`Waiter::wait_with`].
+    #[inline]
+    pub fn wait_with(self, tuning: Tuning) {
+        let _use = self.0.waiting.enter("StaticWaiter");
+        self.0.state.wait_with(&self.0.event, tuning);
+    }
+
+    /// See [`Waiter::wait_timeout`].
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn wait_timeout(self, timeout: Duration) -> bool {
+        let _use = self.0.waiting.enter("StaticWaiter");
+        self.0.state.wait_timeout(&self.0.event, timeout)
+    }
+
+    /// See [`Waiter::try_wait`].
+    #[inline]
+    pub fn try_wait(self) -> bool {
+        let _use = self.0.waiting.enter("StaticWaiter");
+        self.0.state.try_wait(&self.0.event)
+    }
+}
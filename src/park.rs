@@ -0,0 +1,188 @@
+//! Pluggable parking backend.
+//!
+//! Once the spin phases are exhausted, a [`Waiter`](crate::Waiter) blocks on
+//! a 32-bit wake word. With the `std` feature (enabled by default) this uses
+//! the platform's native address-based wait. Without `std`, or on platforms
+//! where that isn't appropriate (bare metal, RTOS), a [`ParkHooks`]
+//! implementation can be installed once via [`set_park_hooks`].
+//!
+//! Without `std` and without hooks, waiters never block and keep spinning.
+//!
+//! # Example
+//!
+//! ```
+//! use core::sync::atomic::AtomicU32;
+//! use waitx::ParkHooks;
+//!
+//! struct Spin;
+//!
+//! impl ParkHooks for Spin {
+//!     fn wait(&self, _atomic: &AtomicU32, _value: u32) {
+//!         core::hint::spin_loop();
+//!     }
+//!
+//!     fn wake_one(&self, _atomic: &AtomicU32) {}
+//! }
+//!
+//! waitx::set_park_hooks(&Spin).unwrap();
+//! ```
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::sync::atomic::{AtomicU8, AtomicU32, Ordering};
+use core::time::Duration;
+
+/// Hooks used to block and wake threads when the spin phases are exhausted.
+pub trait ParkHooks: Sync {
+    /// Blocks the current thread while `atomic` holds `value`.
+    ///
+    /// May return spuriously, the caller re-checks its condition.
+    fn wait(&self, atomic: &AtomicU32, value: u32);
+
+    /// Like [`ParkHooks::wait`], but returns once `timeout` has elapsed.
+    ///
+    /// Used by waiters with a watchdog (see `Waiter::set_watchdog`).
+    /// Defaults to [`ParkHooks::wait`], ignoring the timeout.
+    fn wait_timeout(&self, atomic: &AtomicU32, value: u32, timeout: Duration) {
+        let _ = timeout;
+        self.wait(atomic, value);
+    }
+
+    /// Wakes one thread blocked in [`ParkHooks::wait`] on `atomic`.
+    fn wake_one(&self, atomic: &AtomicU32);
+
+    /// Wakes one thread blocked on `atomic` from interrupt context.
+    ///
+    /// Used by [`Waker::signal_from_isr`](crate::Waker::signal_from_isr).
+    /// Implementations must not lock or allocate, e.g. by pending a
+    /// scheduler interrupt or an RTOS "give from ISR" call. Defaults to
+    /// [`ParkHooks::wake_one`].
+    fn wake_from_isr(&self, atomic: &AtomicU32) {
+        self.wake_one(atomic);
+    }
+
+    /// Gives up the remainder of the current time slice.
+    fn yield_now(&self) {
+        core::hint::spin_loop();
+    }
+}
+
+/// Error returned by [`set_park_hooks`] when hooks were already installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetParkHooksError(());
+
+impl fmt::Display for SetParkHooksError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("park hooks have already been set")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SetParkHooksError {}
+
+const UNSET: u8 = 0;
+const INITIALIZING: u8 = 1;
+const SET: u8 = 2;
+
+struct HooksCell(UnsafeCell<Option<&'static dyn ParkHooks>>);
+
+// SAFETY: the cell is written exactly once, before `STATE` is published as `SET`.
+unsafe impl Sync for HooksCell {}
+
+static STATE: AtomicU8 = AtomicU8::new(UNSET);
+static HOOKS: HooksCell = HooksCell(UnsafeCell::new(None));
+
+/// Installs the process-wide parking hooks.
+///
+/// Hooks can only be set once; later calls return an error. They take
+/// precedence over the `std` backend.
+pub fn set_park_hooks(hooks: &'static dyn ParkHooks) -> Result<(), SetParkHooksError> {
+    if STATE
+        .compare_exchange(UNSET, INITIALIZING, Ordering::Acquire, Ordering::Relaxed)
+        .is_err()
+    {
+        return Err(SetParkHooksError(()));
+    }
+    // SAFETY: the `INITIALIZING` state grants exclusive access to the cell.
+    unsafe { *HOOKS.0.get() = Some(hooks) };
+    STATE.store(SET, Ordering::Release);
+    Ok(())
+}
+
+#[cfg(not(feature = "loom"))]
+#[inline(always)]
+fn hooks() -> Option<&'static dyn ParkHooks> {
+    if STATE.load(Ordering::Acquire) == SET {
+        // SAFETY: the cell is never written again once `SET` is observed.
+        unsafe { *HOOKS.0.get() }
+    } else {
+        None
+    }
+}
+
+/// Blocks while `atomic` holds `value`, possibly returning spuriously.
+#[cfg(not(feature = "loom"))]
+#[inline]
+pub(crate) fn wait(atomic: &AtomicU32, value: u32) {
+    if let Some(hooks) = hooks() {
+        return hooks.wait(atomic, value);
+    }
+
+    #[cfg(feature = "std")]
+    crate::atomic_wait::wait(atomic, value);
+
+    #[cfg(not(feature = "std"))]
+    core::hint::spin_loop();
+}
+
+/// Like [`wait`], but returns once `timeout` has elapsed.
+#[cfg(all(feature = "std", not(feature = "loom")))]
+#[inline]
+pub(crate) fn wait_timeout(atomic: &AtomicU32, value: u32, timeout: Duration) {
+    if let Some(hooks) = hooks() {
+        return hooks.wait_timeout(atomic, value, timeout);
+    }
+
+    crate::atomic_wait::wait_timeout(atomic, value, timeout);
+}
+
+/// Wakes one thread blocked on `atomic`.
+#[cfg(not(feature = "loom"))]
+#[inline]
+pub(crate) fn wake_one(atomic: &AtomicU32) {
+    match hooks() {
+        Some(hooks) => hooks.wake_one(atomic),
+        #[cfg(feature = "std")]
+        None => crate::atomic_wait::wake_one(atomic),
+        #[cfg(not(feature = "std"))]
+        None => {}
+    }
+}
+
+/// Wakes one thread blocked on `atomic` from interrupt context.
+#[cfg(not(feature = "loom"))]
+#[inline]
+pub(crate) fn wake_from_isr(atomic: &AtomicU32) {
+    match hooks() {
+        Some(hooks) => hooks.wake_from_isr(atomic),
+        #[cfg(feature = "std")]
+        None => crate::atomic_wait::wake_one(atomic),
+        #[cfg(not(feature = "std"))]
+        None => {}
+    }
+}
+
+/// Yields the current time slice.
+#[cfg(not(feature = "loom"))]
+#[inline]
+pub(crate) fn yield_now() {
+    if let Some(hooks) = hooks() {
+        return hooks.yield_now();
+    }
+
+    #[cfg(feature = "std")]
+    std::thread::yield_now();
+
+    #[cfg(not(feature = "std"))]
+    core::hint::spin_loop();
+}
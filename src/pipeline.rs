@@ -0,0 +1,120 @@
+//! Stream-processing stages connected by channels.
+//!
+//! A stage is a worker thread that receives items from one channel,
+//! transforms them and sends the results into a new channel. [`stage`] spawns
+//! one; [`Pipeline`] chains several. Closing propagates: a stage closes its
+//! output once its input is closed and drained, and closes its input if its
+//! output is closed, so shutting down either end stops every stage. A stage
+//! waiting for input notices a closed output with the next item.
+//!
+//! # Example
+//!
+//! ```
+//! use waitx::pipeline::Pipeline;
+//!
+//! let (tx, rx) = waitx::channel();
+//! let (out, workers) = Pipeline::new(rx)
+//!     .stage(|x: u32| x + 1)
+//!     .stage(|x| x * 2)
+//!     .into_parts();
+//!
+//! std::thread::spawn(move || {
+//!     tx.send(1);
+//!     tx.send(2);
+//!     tx.close();
+//! });
+//! assert_eq!(out.iter().collect::<Vec<_>>(), [4, 6]);
+//! workers.into_iter().for_each(|w| w.join().unwrap());
+//! ```
+
+use crate::prelude::*;
+
+use alloc::vec::Vec;
+use std::thread::JoinHandle;
+
+/// Spawns a worker that applies `f` to every item from `rx` and sends the
+/// results into the returned receiver.
+pub fn stage<I, O>(
+    rx: Receiver<I>,
+    f: impl FnMut(I) -> O + Send + 'static,
+) -> (Receiver<O>, JoinHandle<()>)
+where
+    I: Send + 'static,
+    O: Send + 'static,
+{
+    spawn(rx, None, f)
+}
+
+fn spawn<I, O>(
+    rx: Receiver<I>,
+    tuning: Option<Tuning>,
+    mut f: impl FnMut(I) -> O + Send + 'static,
+) -> (Receiver<O>, JoinHandle<()>)
+where
+    I: Send + 'static,
+    O: Send + 'static,
+{
+    let (tx, out) = channel();
+    let worker = std::thread::spawn(move || {
+        if let Some(tuning) = tuning {
+            set_thread_tuning(tuning);
+        }
+        for item in &rx {
+            if tx.send_unless_closed(f(item)).is_err() {
+                // downstream is gone, stop upstream too
+                rx.close();
+                return;
+            }
+        }
+        tx.close();
+    });
+    (out, worker)
+}
+
+/// A chain of [`stage`]s, built one stage at a time.
+pub struct Pipeline<T> {
+    rx: Receiver<T>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl<T: Send + 'static> Pipeline<T> {
+    /// Starts a pipeline that reads from `rx`.
+    pub fn new(rx: Receiver<T>) -> Self {
+        Self {
+            rx,
+            workers: Vec::new(),
+        }
+    }
+
+    /// Appends a stage applying `f` to every item.
+    pub fn stage<O: Send + 'static>(self, f: impl FnMut(T) -> O + Send + 'static) -> Pipeline<O> {
+        self.push(None, f)
+    }
+
+    /// Appends a stage applying `f` to every item, whose worker thread
+    /// blocks with `tuning`; see [`set_thread_tuning`].
+    pub fn stage_with_tuning<O: Send + 'static>(
+        self,
+        tuning: Tuning,
+        f: impl FnMut(T) -> O + Send + 'static,
+    ) -> Pipeline<O> {
+        self.push(Some(tuning), f)
+    }
+
+    fn push<O: Send + 'static>(
+        self,
+        tuning: Option<Tuning>,
+        f: impl FnMut(T) -> O + Send + 'static,
+    ) -> Pipeline<O> {
+        let Pipeline { rx, mut workers } = self;
+        let (rx, worker) = spawn(rx, tuning, f);
+        workers.push(worker);
+        Pipeline { rx, workers }
+    }
+
+    /// Returns the receiver of the last stage's output and the stages'
+    /// worker threads, in order.
+    pub fn into_parts(self) -> (Receiver<T>, Vec<JoinHandle<()>>) {
+        (self.rx, self.workers)
+    }
+}
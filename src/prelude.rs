@@ -1,22 +1,49 @@
 #![allow(unused_imports)]
 
-pub use std::cell::UnsafeCell;
-pub use std::mem::MaybeUninit;
+pub use core::cell::UnsafeCell;
+pub use core::mem::MaybeUninit;
 
 #[cfg(feature = "loom")]
 pub use loom::{
-    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    sync::atomic::{AtomicBool, AtomicU32, AtomicU64 as AtomicCount, AtomicU64, AtomicUsize},
     sync::{Arc, Condvar, Mutex},
     thread,
 };
 
+#[cfg(all(feature = "loom", not(feature = "seqcst-debug")))]
+pub use loom::sync::atomic::Ordering;
+
 #[cfg(not(feature = "loom"))]
-pub use std::{
-    sync::Arc,
-    sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
-    thread,
-};
+pub use alloc::sync::Arc;
+
+#[cfg(not(feature = "loom"))]
+pub use core::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize};
+
+#[cfg(all(not(feature = "loom"), not(feature = "seqcst-debug")))]
+pub use core::sync::atomic::Ordering;
+
+/// Stands in for [`core::sync::atomic::Ordering`] under `seqcst-debug`, so
+/// every ordering named through the prelude is `SeqCst`.
+#[cfg(feature = "seqcst-debug")]
+pub struct Ordering;
+
+#[cfg(feature = "seqcst-debug")]
+#[allow(non_upper_case_globals, dead_code)]
+impl Ordering {
+    pub const Relaxed: core::sync::atomic::Ordering = core::sync::atomic::Ordering::SeqCst;
+    pub const Release: core::sync::atomic::Ordering = core::sync::atomic::Ordering::SeqCst;
+    pub const Acquire: core::sync::atomic::Ordering = core::sync::atomic::Ordering::SeqCst;
+    pub const AcqRel: core::sync::atomic::Ordering = core::sync::atomic::Ordering::SeqCst;
+    pub const SeqCst: core::sync::atomic::Ordering = core::sync::atomic::Ordering::SeqCst;
+}
+
+#[cfg(all(not(feature = "loom"), target_has_atomic = "64"))]
+pub use core::sync::atomic::AtomicU64 as AtomicCount;
+
+#[cfg(all(not(feature = "loom"), not(target_has_atomic = "64")))]
+pub use core::sync::atomic::AtomicU32 as AtomicCount;
 
 pub use crate::channel::*;
+pub use crate::oneshot::*;
 pub use crate::pair::*;
 pub use crate::util::*;
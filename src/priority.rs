@@ -0,0 +1,115 @@
+//! Wake-time priority boosting for parked waiters on Windows.
+//!
+//! A waiter registers its thread while blocked. The first waker to find it
+//! registered raises that thread to `THREAD_PRIORITY_HIGHEST` right before
+//! waking it, so the scheduler runs it sooner; the waiter restores its
+//! previous priority as soon as it returns.
+
+use core::sync::atomic::{AtomicI32, AtomicU32, AtomicUsize, Ordering};
+use std::os::windows::io::{AsRawHandle, FromRawHandle, OwnedHandle};
+use windows_sys::Win32::Foundation::FALSE;
+use windows_sys::Win32::System::Threading::{
+    GetCurrentThread, GetCurrentThreadId, GetThreadPriority, OpenThread, SetThreadPriority,
+    THREAD_PRIORITY_HIGHEST, THREAD_QUERY_LIMITED_INFORMATION, THREAD_SET_LIMITED_INFORMATION,
+};
+
+/// `THREAD_PRIORITY_ERROR_RETURN`: also used as "no boost applied".
+const NONE: i32 = i32::MAX;
+
+thread_local! {
+    // A real handle to the current thread, usable by wakers on other threads.
+    static THREAD: Option<OwnedHandle> = {
+        let handle = unsafe {
+            OpenThread(
+                THREAD_QUERY_LIMITED_INFORMATION | THREAD_SET_LIMITED_INFORMATION,
+                FALSE,
+                GetCurrentThreadId(),
+            )
+        };
+        (!handle.is_null()).then(|| unsafe { OwnedHandle::from_raw_handle(handle) })
+    };
+}
+
+pub(crate) struct PriorityBoost {
+    /// The blocked waiter's thread handle, or 0 when none is registered.
+    thread: AtomicUsize,
+    /// Wakers currently inspecting `thread`; the waiter won't return until
+    /// they are done, so the handle stays valid.
+    boosting: AtomicU32,
+    /// The waiter's priority before it was boosted, or [`NONE`].
+    saved: AtomicI32,
+}
+
+impl PriorityBoost {
+    pub(crate) const fn new() -> Self {
+        Self {
+            thread: AtomicUsize::new(0),
+            boosting: AtomicU32::new(0),
+            saved: AtomicI32::new(NONE),
+        }
+    }
+
+    /// Registers the calling thread as blocked until the guard is dropped.
+    #[inline]
+    pub(crate) fn enter(&self) -> PriorityGuard<'_> {
+        let thread = THREAD
+            .try_with(|thread| thread.as_ref().map_or(0, |t| t.as_raw_handle() as usize))
+            .unwrap_or(0);
+        self.thread.store(thread, Ordering::SeqCst);
+        PriorityGuard(self)
+    }
+
+    /// Raises the registered waiter to `THREAD_PRIORITY_HIGHEST`, unless it
+    /// already runs at least that high or was boosted already.
+    #[inline]
+    pub(crate) fn boost(&self) {
+        if self.thread.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+
+        self.boosting.fetch_add(1, Ordering::SeqCst);
+        let thread = self.thread.load(Ordering::SeqCst);
+        if thread != 0 {
+            self.boost_thread(thread as _);
+        }
+        self.boosting.fetch_sub(1, Ordering::Release);
+    }
+
+    #[cold]
+    fn boost_thread(&self, thread: windows_sys::Win32::Foundation::HANDLE) {
+        let current = unsafe { GetThreadPriority(thread) };
+        if current == NONE || current >= THREAD_PRIORITY_HIGHEST {
+            return;
+        }
+        // only the first waker boosts, so `saved` is the original priority
+        if self
+            .saved
+            .compare_exchange(NONE, current, Ordering::AcqRel, Ordering::Relaxed)
+            .is_ok()
+        {
+            unsafe { SetThreadPriority(thread, THREAD_PRIORITY_HIGHEST) };
+        }
+    }
+
+    fn leave(&self) {
+        self.thread.store(0, Ordering::SeqCst);
+        while self.boosting.load(Ordering::SeqCst) != 0 {
+            core::hint::spin_loop();
+        }
+
+        let saved = self.saved.swap(NONE, Ordering::AcqRel);
+        if saved != NONE {
+            unsafe { SetThreadPriority(GetCurrentThread(), saved) };
+        }
+    }
+}
+
+/// Unregisters the waiter and restores its priority if it was boosted.
+pub(crate) struct PriorityGuard<'a>(&'a PriorityBoost);
+
+impl Drop for PriorityGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.leave();
+    }
+}
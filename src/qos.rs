@@ -0,0 +1,126 @@
+//! QoS propagation from wakers to parked waiters on Apple platforms.
+//!
+//! A waiter registers its thread while blocked. A waker running at a higher
+//! QoS class than that thread starts a `pthread` QoS override on it, so the
+//! kernel schedules the waiter at the waker's class until it has woken up
+//! and ended the override.
+
+use core::ffi::{c_int, c_void};
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, AtomicU32, AtomicUsize, Ordering};
+
+type QosClass = u32;
+type Override = *mut c_void;
+
+const QOS_CLASS_UNSPECIFIED: QosClass = 0;
+
+// Declared in <pthread/qos.h> and <sys/qos.h>; part of libSystem since
+// macOS 10.10 / iOS 8.
+unsafe extern "C" {
+    fn qos_class_self() -> QosClass;
+
+    fn pthread_get_qos_class_np(
+        thread: libc::pthread_t,
+        class: *mut QosClass,
+        relpri: *mut c_int,
+    ) -> c_int;
+
+    fn pthread_override_qos_class_start_np(
+        thread: libc::pthread_t,
+        class: QosClass,
+        relpri: c_int,
+    ) -> Override;
+
+    fn pthread_override_qos_class_end_np(over: Override) -> c_int;
+}
+
+pub(crate) struct QosOverride {
+    /// The blocked waiter's `pthread_t`, or 0 when none is registered.
+    thread: AtomicUsize,
+    /// Wakers currently inspecting `thread`; the waiter won't return until
+    /// they are done, so the handle stays valid.
+    boosting: AtomicU32,
+    /// The override started on the waiter, ended once it wakes.
+    active: AtomicPtr<c_void>,
+}
+
+impl QosOverride {
+    pub(crate) const fn new() -> Self {
+        Self {
+            thread: AtomicUsize::new(0),
+            boosting: AtomicU32::new(0),
+            active: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Registers the calling thread as blocked until the guard is dropped.
+    #[inline]
+    pub(crate) fn enter(&self) -> QosGuard<'_> {
+        let thread = unsafe { libc::pthread_self() };
+        self.thread.store(thread as usize, Ordering::SeqCst);
+        QosGuard(self)
+    }
+
+    /// Lifts the registered waiter to the calling thread's QoS class, if
+    /// that is higher.
+    #[inline]
+    pub(crate) fn boost(&self) {
+        if self.thread.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+
+        self.boosting.fetch_add(1, Ordering::SeqCst);
+        let thread = self.thread.load(Ordering::SeqCst);
+        if thread != 0 {
+            self.boost_thread(thread as libc::pthread_t);
+        }
+        self.boosting.fetch_sub(1, Ordering::Release);
+    }
+
+    #[cold]
+    fn boost_thread(&self, thread: libc::pthread_t) {
+        let class = unsafe { qos_class_self() };
+        if class == QOS_CLASS_UNSPECIFIED {
+            return;
+        }
+
+        let mut current = QOS_CLASS_UNSPECIFIED;
+        let mut relpri = 0;
+        if unsafe { pthread_get_qos_class_np(thread, &mut current, &mut relpri) } != 0
+            || current >= class
+        {
+            return;
+        }
+
+        let over = unsafe { pthread_override_qos_class_start_np(thread, class, 0) };
+        if over.is_null() {
+            return;
+        }
+        let prev = self.active.swap(over, Ordering::AcqRel);
+        if !prev.is_null() {
+            unsafe { pthread_override_qos_class_end_np(prev) };
+        }
+    }
+
+    fn leave(&self) {
+        self.thread.store(0, Ordering::SeqCst);
+        while self.boosting.load(Ordering::SeqCst) != 0 {
+            core::hint::spin_loop();
+        }
+
+        let over = self.active.swap(ptr::null_mut(), Ordering::AcqRel);
+        if !over.is_null() {
+            unsafe { pthread_override_qos_class_end_np(over) };
+        }
+    }
+}
+
+/// Unregisters the waiter and ends any override started on it.
+pub(crate) struct QosGuard<'a>(&'a QosOverride);
+
+impl Drop for QosGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.leave();
+    }
+}
@@ -0,0 +1,186 @@
+//! A request/response channel.
+//!
+//! [`channel`] hands out a [`Client`], whose [`call`](Client::call) sends a
+//! request and blocks for its response, and a [`Server`], which answers
+//! requests with [`serve`](Server::serve). It replaces the usual pair of
+//! channels, one per direction, with two slots and one event each way: calls
+//! strictly alternate, so neither side ever waits for a slot to empty.
+//!
+//! # Example
+//!
+//! ```
+//! let (client, server) = waitx::rpc::channel::<u32, u32>();
+//!
+//! let handle = std::thread::spawn(move || server.serve(|x| x * 2));
+//! assert_eq!(client.call(21), 42);
+//! client.close();
+//! handle.join().unwrap();
+//! ```
+
+use crate::prelude::*;
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+
+/// The state both sides of an rpc channel share.
+struct Shared<Req, Resp> {
+    request: Slot<Req>,
+    response: Slot<Resp>,
+    /// Signaled by the client once a request is in its slot.
+    requested: Event,
+    /// Signaled by the server once the response is in its slot.
+    responded: Event,
+    calling: ThreadTag,
+    serving: ThreadTag,
+    /// Set once either side closed the channel.
+    closed: AtomicBool,
+}
+
+/// Calling side of an rpc [`channel`].
+///
+/// A client can be moved to another thread but not shared between threads,
+/// since calls use the single request slot one at a time:
+///
+/// ```compile_fail
+/// fn assert_sync<T: Sync>() {}
+/// assert_sync::<waitx::rpc::Client<u32, u32>>();
+/// ```
+pub struct Client<Req, Resp> {
+    shared: Arc<Shared<Req, Resp>>,
+    state: WaitState,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<Req, Resp> Client<Req, Resp> {
+    /// Sends `req` and blocks until the server responds.
+    ///
+    /// Blocks forever if the server is kept around but stops serving.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel was closed with [`close`](Self::close), or if
+    /// the server is dropped without responding, e.g. because its handler
+    /// panicked.
+    #[inline]
+    pub fn call(&self, req: Req) -> Resp {
+        let _use = self.shared.calling.enter("rpc Client");
+        let shared = &*self.shared;
+        if shared.closed.load(Ordering::Acquire) {
+            panic!("waitx: call on a closed rpc channel");
+        }
+
+        // SAFETY: the server took the previous request before responding to
+        // it, so the client owns the empty request slot.
+        unsafe {
+            shared.request.write(req);
+        }
+        shared.request.mark_full();
+        shared.requested.signal();
+
+        let closed = || shared.closed.load(Ordering::Acquire);
+        if !self
+            .state
+            .wait_unless(&shared.responded, self.state.tuning(), closed)
+        {
+            panic!("waitx: rpc server dropped without responding");
+        }
+        // SAFETY: consuming `responded` handed us the full response slot.
+        let resp = unsafe { shared.response.read() };
+        shared.response.mark_empty();
+        resp
+    }
+
+    /// Closes the channel, so [`Server::serve`] returns once it has answered
+    /// any call in progress. Dropping the client does the same.
+    pub fn close(&self) {
+        self.shared.closed.store(true, Ordering::SeqCst);
+        self.shared.requested.signal_n(0);
+    }
+}
+
+impl<Req, Resp> Drop for Client<Req, Resp> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// Serving side of an rpc [`channel`].
+///
+/// Like the [`Client`], it can be moved to another thread but not shared.
+pub struct Server<Req, Resp> {
+    shared: Arc<Shared<Req, Resp>>,
+    state: WaitState,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<Req, Resp> Server<Req, Resp> {
+    /// Answers requests with `f` until the client closes the channel or is
+    /// dropped.
+    pub fn serve(&self, mut f: impl FnMut(Req) -> Resp) {
+        while self.serve_one(&mut f) {}
+    }
+
+    /// Blocks for the next request and answers it with `f`. Returns `false`
+    /// instead if the client closed the channel.
+    #[inline]
+    pub fn serve_one(&self, f: impl FnOnce(Req) -> Resp) -> bool {
+        let _use = self.shared.serving.enter("rpc Server");
+        let shared = &*self.shared;
+        let closed = || shared.closed.load(Ordering::Acquire);
+        if !self
+            .state
+            .wait_unless(&shared.requested, self.state.tuning(), closed)
+        {
+            return false;
+        }
+
+        // SAFETY: consuming `requested` handed us the full request slot.
+        let req = unsafe { shared.request.read() };
+        shared.request.mark_empty();
+        let resp = f(req);
+
+        // SAFETY: the client took the previous response before sending this
+        // request, so the server owns the empty response slot.
+        unsafe {
+            shared.response.write(resp);
+        }
+        shared.response.mark_full();
+        shared.responded.signal();
+        true
+    }
+}
+
+impl<Req, Resp> Drop for Server<Req, Resp> {
+    fn drop(&mut self) {
+        // fails a call in progress that wasn't answered
+        self.shared.closed.store(true, Ordering::SeqCst);
+        self.shared.responded.signal_n(0);
+    }
+}
+
+/// Creates a request/response channel; see the [module docs](self).
+///
+/// Both sides block using the calling thread's tuning, like
+/// [`channel`](crate::channel()).
+pub fn channel<Req, Resp>() -> (Client<Req, Resp>, Server<Req, Resp>) {
+    let shared = Arc::new(Shared {
+        request: Slot::default(),
+        response: Slot::default(),
+        requested: Event::new(),
+        responded: Event::new(),
+        calling: ThreadTag::new(),
+        serving: ThreadTag::new(),
+        closed: AtomicBool::new(false),
+    });
+    let client = Client {
+        shared: shared.clone(),
+        state: WaitState::new(TuningSource::Thread),
+        _not_sync: PhantomData,
+    };
+    let server = Server {
+        shared,
+        state: WaitState::new(TuningSource::Thread),
+        _not_sync: PhantomData,
+    };
+    (client, server)
+}
@@ -0,0 +1,93 @@
+//! A duplex channel whose endpoint types track whose turn it is.
+//!
+//! [`channel`] hands one side a [`SendState`] and the other a
+//! [`RecvState`]. Sending consumes a `SendState` and returns a `RecvState`,
+//! receiving does the opposite, so the two sides always alternate: they
+//! can't both be waiting to receive, or both sending into the single slot
+//! of the same direction, because only one of them holds a `SendState` at a
+//! time.
+//!
+//! # Example
+//!
+//! ```
+//! let (client, server) = waitx::session::channel::<u32, String>();
+//!
+//! let handle = std::thread::spawn(move || {
+//!     let mut server = server;
+//!     for _ in 0..2 {
+//!         let (n, reply) = server.recv();
+//!         server = reply.send(n.to_string());
+//!     }
+//! });
+//!
+//! let (reply, client) = client.send(1).recv();
+//! assert_eq!(reply, "1");
+//! let (reply, _) = client.send(2).recv();
+//! assert_eq!(reply, "2");
+//! handle.join().unwrap();
+//! ```
+
+use crate::prelude::*;
+
+/// A session endpoint whose turn it is to send an `S`, then receive an `R`.
+#[must_use = "dropping a session endpoint ends the protocol"]
+pub struct SendState<S, R> {
+    tx: Sender<S>,
+    rx: Receiver<R>,
+}
+
+impl<S, R> SendState<S, R> {
+    /// Sends `value` and hands the turn to the peer.
+    ///
+    /// Never blocks for long: the peer took the previous value before
+    /// replying to it.
+    #[inline]
+    pub fn send(self, value: S) -> RecvState<S, R> {
+        self.tx.send(value);
+        RecvState {
+            tx: self.tx,
+            rx: self.rx,
+        }
+    }
+}
+
+/// A session endpoint waiting to receive an `R` before it may send an `S`.
+#[must_use = "dropping a session endpoint ends the protocol"]
+pub struct RecvState<S, R> {
+    tx: Sender<S>,
+    rx: Receiver<R>,
+}
+
+impl<S, R> RecvState<S, R> {
+    /// Blocks until the peer sends, and returns the value along with the
+    /// turn to reply.
+    #[inline]
+    pub fn recv(self) -> (R, SendState<S, R>) {
+        let value = self.rx.recv();
+        let next = SendState {
+            tx: self.tx,
+            rx: self.rx,
+        };
+        (value, next)
+    }
+
+    /// Returns whether the peer has sent, so [`recv`](Self::recv) returns
+    /// right away.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.rx.is_full()
+    }
+}
+
+/// Creates a session between a side that sends `A` first and one that
+/// receives it first and replies with `B`.
+///
+/// Both sides block using the calling thread's tuning, like
+/// [`channel`](crate::channel()).
+pub fn channel<A, B>() -> (SendState<A, B>, RecvState<B, A>) {
+    let (a_tx, a_rx) = crate::channel();
+    let (b_tx, b_rx) = crate::channel();
+    let first = SendState { tx: a_tx, rx: b_rx };
+    let second = RecvState { tx: b_tx, rx: a_rx };
+    (first, second)
+}
@@ -0,0 +1,102 @@
+//! POSIX signal support.
+//!
+//! [`Waker::signal_from_signal_handler`](crate::Waker::signal_from_signal_handler)
+//! can be called from any signal handler. With the `signal-hook` feature,
+//! [`signal_on`] installs such handlers directly, so a thread blocked in
+//! `wait()`/`recv()` can react to e.g. shutdown signals without a sidecar
+//! thread.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "signal-hook")] {
+//! let (waker, waiter) = waitx::pair();
+//! let _guard = waitx::signal::signal_on(waker, waitx::signal::SHUTDOWN_SIGNALS).unwrap();
+//!
+//! waiter.wait(); // returns on SIGINT or SIGTERM
+//! # }
+//! ```
+
+#[cfg(feature = "signal-hook")]
+use crate::Waker;
+
+/// The signals conventionally used to request a graceful shutdown.
+#[cfg(feature = "signal-hook")]
+pub const SHUTDOWN_SIGNALS: &[libc::c_int] = &[libc::SIGINT, libc::SIGTERM];
+
+/// Registers handlers that signal `waker` whenever one of `signals` is
+/// delivered to the process.
+///
+/// Handlers are added alongside any existing ones and stay installed until
+/// the returned guard is dropped. Fails for signals that cannot be handled
+/// (`SIGKILL`, `SIGSTOP`) or that are unsafe to handle (`SIGSEGV`, ...).
+#[cfg(feature = "signal-hook")]
+pub fn signal_on(waker: Waker, signals: &[libc::c_int]) -> std::io::Result<SignalGuard> {
+    let mut guard = SignalGuard(Vec::with_capacity(signals.len()));
+    for &signal in signals {
+        let waker = waker.clone();
+        // SAFETY: `signal_from_signal_handler` is async-signal-safe.
+        let id = unsafe {
+            signal_hook_registry::register(signal, move || waker.signal_from_signal_handler())?
+        };
+        guard.0.push(id);
+    }
+    Ok(guard)
+}
+
+/// Keeps the handlers installed by [`signal_on`] registered; dropping it
+/// removes them.
+#[cfg(feature = "signal-hook")]
+#[must_use = "dropping the guard unregisters the handlers"]
+pub struct SignalGuard(Vec<signal_hook_registry::SigId>);
+
+#[cfg(feature = "signal-hook")]
+impl Drop for SignalGuard {
+    fn drop(&mut self) {
+        for id in self.0.drain(..) {
+            signal_hook_registry::unregister(id);
+        }
+    }
+}
+
+/// Saves `errno` on creation and restores it on drop, so code running inside a
+/// signal handler doesn't clobber the interrupted thread's `errno`.
+pub(crate) struct ErrnoGuard(libc::c_int);
+
+impl ErrnoGuard {
+    #[inline(always)]
+    pub fn new() -> Self {
+        // SAFETY: the errno location is valid for the lifetime of the thread.
+        Self(unsafe { *errno_location() })
+    }
+}
+
+impl Drop for ErrnoGuard {
+    #[inline(always)]
+    fn drop(&mut self) {
+        // SAFETY: the errno location is valid for the lifetime of the thread.
+        unsafe { *errno_location() = self.0 };
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "emscripten", target_os = "redox"))]
+use libc::__errno_location as errno_location;
+
+#[cfg(target_os = "android")]
+use libc::__errno as errno_location;
+
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "tvos",
+    target_os = "watchos",
+    target_os = "visionos",
+    target_os = "freebsd"
+))]
+use libc::__error as errno_location;
+
+#[cfg(any(target_os = "netbsd", target_os = "openbsd"))]
+use libc::__errno as errno_location;
+
+#[cfg(any(target_os = "solaris", target_os = "illumos"))]
+use libc::___errno as errno_location;
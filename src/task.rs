@@ -0,0 +1,196 @@
+//! Running closures on a dedicated worker thread.
+//!
+//! [`task_channel`] hands out a [`TaskSender`], whose
+//! [`submit`](TaskSender::submit) sends a closure to the worker and returns a
+//! [`JoinTicket`] for its result, and a [`TaskWorker`] that runs the
+//! closures in order, like a minimal single-thread executor.
+//!
+//! # Example
+//!
+//! ```
+//! let (tasks, worker) = waitx::task_channel();
+//! let handle = std::thread::spawn(move || worker.run());
+//!
+//! let ticket = tasks.submit(|| 6 * 7);
+//! assert_eq!(ticket.join(), 42);
+//!
+//! tasks.close();
+//! handle.join().unwrap();
+//! ```
+
+use crate::prelude::*;
+
+use alloc::boxed::Box;
+
+type Task = Box<dyn FnOnce() + Send>;
+
+/// Submitting side of a [`task_channel`].
+pub struct TaskSender {
+    tx: Sender<Task>,
+}
+
+impl TaskSender {
+    /// Sends `f` to the worker and returns a ticket for its result.
+    ///
+    /// Blocks until the worker has taken the previously submitted task.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel is closed.
+    pub fn submit<R: Send + 'static>(
+        &self,
+        f: impl FnOnce() -> R + Send + 'static,
+    ) -> JoinTicket<R> {
+        let result = Arc::new(TicketState {
+            slot: Slot::default(),
+            done: Event::new(),
+            cancelled: AtomicBool::new(false),
+        });
+        let ticket = JoinTicket {
+            result: result.clone(),
+            wait: WaitState::new(TuningSource::Thread),
+        };
+        let completion = Completion {
+            result,
+            delivered: false,
+        };
+        self.tx.send(Box::new(move || completion.deliver(f())));
+        ticket
+    }
+
+    /// Closes the channel: the worker runs the task already submitted, if
+    /// any, and then [`TaskWorker::run`] returns. Dropping the sender does
+    /// the same.
+    pub fn close(&self) {
+        self.tx.close();
+    }
+}
+
+/// Running side of a [`task_channel`].
+pub struct TaskWorker {
+    rx: Receiver<Task>,
+}
+
+impl TaskWorker {
+    /// Runs submitted tasks in order until the sender closes the channel or
+    /// is dropped.
+    ///
+    /// A panicking task unwinds out of `run`, and its ticket's
+    /// [`join`](JoinTicket::join) panics as well.
+    pub fn run(&self) {
+        for task in &self.rx {
+            task();
+        }
+    }
+
+    /// Runs the next task if one was submitted, without blocking. Returns
+    /// whether one ran.
+    pub fn run_pending(&self) -> bool {
+        let Some(task) = self.rx.try_recv() else {
+            return false;
+        };
+        task();
+        true
+    }
+}
+
+impl Drop for TaskWorker {
+    fn drop(&mut self) {
+        // a task submitted but never run cancels its ticket
+        drop(self.rx.try_recv());
+    }
+}
+
+/// Where a task's result is left for its [`JoinTicket`].
+struct TicketState<R> {
+    slot: Slot<R>,
+    done: Event,
+    /// Set if the task was dropped without delivering a result.
+    cancelled: AtomicBool,
+}
+
+/// The task's side of a ticket, cancelling it if the task is dropped
+/// without running or panics.
+struct Completion<R> {
+    result: Arc<TicketState<R>>,
+    delivered: bool,
+}
+
+impl<R> Completion<R> {
+    fn deliver(mut self, value: R) {
+        let result = &*self.result;
+        // SAFETY: the task runs once, and the ticket doesn't read the slot
+        // before `done` is signaled.
+        unsafe {
+            result.slot.write(value);
+        }
+        result.slot.mark_full();
+        result.done.signal();
+        self.delivered = true;
+    }
+}
+
+impl<R> Drop for Completion<R> {
+    fn drop(&mut self) {
+        if !self.delivered {
+            self.result.cancelled.store(true, Ordering::SeqCst);
+            self.result.done.signal_n(0);
+        }
+    }
+}
+
+/// The result of a task sent with [`TaskSender::submit`], once it ran.
+pub struct JoinTicket<R> {
+    result: Arc<TicketState<R>>,
+    wait: WaitState,
+}
+
+impl<R> JoinTicket<R> {
+    /// Blocks until the task ran and returns its result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the task was dropped without running, e.g. along with the
+    /// worker, or panicked itself.
+    #[inline]
+    pub fn join(self) -> R {
+        let result = &*self.result;
+        let cancelled = || result.cancelled.load(Ordering::Acquire);
+        if !self
+            .wait
+            .wait_unless(&result.done, self.wait.tuning(), cancelled)
+        {
+            panic!("waitx: task dropped without running");
+        }
+        self.take()
+    }
+
+    /// Returns the result if the task has run, without blocking.
+    ///
+    /// Returns `None` again once the result has been taken, and for good
+    /// if the task was dropped without running.
+    #[inline]
+    pub fn try_join(&mut self) -> Option<R> {
+        if !self.wait.try_wait(&self.result.done) {
+            return None;
+        }
+        Some(self.take())
+    }
+
+    #[inline(always)]
+    fn take(&self) -> R {
+        let slot = &self.result.slot;
+        slot.mark_empty();
+        // SAFETY: the task filled the slot before signaling, and won't touch
+        // it again.
+        unsafe { slot.read() }
+    }
+}
+
+/// Creates a channel of tasks for a dedicated worker: submit closures
+/// through the [`TaskSender`], and call [`TaskWorker::run`] on the thread
+/// that should run them.
+pub fn task_channel() -> (TaskSender, TaskWorker) {
+    let (tx, rx) = channel();
+    (TaskSender { tx }, TaskWorker { rx })
+}
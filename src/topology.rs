@@ -0,0 +1,126 @@
+//! Thread-per-core setups: one pinned worker per CPU, all connected.
+//!
+//! [`spawn_per_core`] spawns a worker per given CPU, pins it there, and hands
+//! it a [`Core`]: its place in a [`mesh`](crate::mesh) of channels to and
+//! from every other worker. Each channel is tuned for the two CPUs it
+//! connects, see [`Tuning::for_cpus`].
+//!
+//! Waiters need no registration with the thread that waits on them, so
+//! endpoints work on whichever worker they are moved to.
+//!
+//! # Example
+//!
+//! ```
+//! let workers = waitx::topology::spawn_per_core(&[0, 0], |core: waitx::topology::Core<usize>| {
+//!     let peer = 1 - core.id();
+//!     core.sender(peer).unwrap().send(core.id());
+//!     core.receiver(peer).unwrap().recv()
+//! });
+//! let got: Vec<usize> = workers.into_iter().map(|w| w.join().unwrap()).collect();
+//! assert_eq!(got, [1, 0]);
+//! ```
+
+use crate::prelude::*;
+
+use alloc::vec::Vec;
+use core::ops::Deref;
+use std::thread::JoinHandle;
+
+/// A worker's place in a [`spawn_per_core`] setup: the CPU it runs on and
+/// its channels to every other worker, indexed by worker.
+pub struct Core<T> {
+    cpu: usize,
+    pinned: bool,
+    node: MeshNode<T>,
+}
+
+impl<T> Core<T> {
+    /// Returns the CPU the worker was meant to run on.
+    pub fn cpu(&self) -> usize {
+        self.cpu
+    }
+
+    /// Returns whether pinning the worker to its CPU succeeded; see
+    /// [`pin_current_thread`].
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+}
+
+/// Gives access to the worker's channels: [`MeshNode::sender`] and
+/// [`MeshNode::receiver`] by worker index.
+impl<T> Deref for Core<T> {
+    type Target = MeshNode<T>;
+
+    fn deref(&self) -> &MeshNode<T> {
+        &self.node
+    }
+}
+
+/// Spawns one worker per entry of `cpus`, pinned to that CPU, and runs `f`
+/// on each with its [`Core`]. Returns the workers in order.
+///
+/// Worker `i` runs on `cpus[i]` and reaches worker `j` through
+/// `core.sender(j)` / `core.receiver(j)`.
+pub fn spawn_per_core<T, R, F>(cpus: &[usize], f: F) -> Vec<JoinHandle<R>>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(Core<T>) -> R + Send + Sync + 'static,
+{
+    let tuning = thread_tuning();
+    let nodes = crate::channel::mesh_with(cpus.len(), |from, to| {
+        Builder::new()
+            .tuning(tuning.for_cpus(cpus[to], cpus[from]))
+            .build()
+    });
+    let f = Arc::new(f);
+    nodes
+        .into_iter()
+        .map(|node| {
+            let cpu = cpus[node.id()];
+            let f = f.clone();
+            std::thread::spawn(move || {
+                let pinned = pin_current_thread(cpu);
+                f(Core { cpu, pinned, node })
+            })
+        })
+        .collect()
+}
+
+/// Pins the calling thread to logical CPU `cpu`. Returns `false` if that
+/// failed or isn't supported on this platform.
+///
+/// Supported on Linux/Android and Windows.
+pub fn pin_current_thread(cpu: usize) -> bool {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        if cpu >= libc::CPU_SETSIZE as usize {
+            return false;
+        }
+        // SAFETY: `set` is a plain bitmask, valid once zeroed, and `cpu` is
+        // within it.
+        unsafe {
+            let mut set: libc::cpu_set_t = core::mem::zeroed();
+            libc::CPU_SET(cpu, &mut set);
+            libc::sched_setaffinity(0, core::mem::size_of_val(&set), &set) == 0
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::System::Threading::{GetCurrentThread, SetThreadAffinityMask};
+
+        if cpu >= usize::BITS as usize {
+            return false;
+        }
+        // SAFETY: the pseudo handle of the current thread is always valid.
+        unsafe { SetThreadAffinityMask(GetCurrentThread(), 1 << cpu) != 0 }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android", windows)))]
+    {
+        let _ = cpu;
+        false
+    }
+}
@@ -1,28 +1,206 @@
 #[cfg(not(feature = "loom"))]
 use crate::prelude::*;
 
+#[cfg(feature = "std")]
+use core::time::Duration;
+
+/// Event counter width: 64-bit where native 64-bit atomics exist, 32-bit otherwise.
+#[cfg(target_has_atomic = "64")]
+pub type Count = u64;
+
+/// Event counter width: 64-bit where native 64-bit atomics exist, 32-bit otherwise.
+#[cfg(not(target_has_atomic = "64"))]
+pub type Count = u32;
+
+/// Pads and aligns a value to its own cache line, so that stores to it don't
+/// contend with accesses to neighbouring fields (false sharing).
+///
+/// x86-64 and aarch64 prefetch cache lines in pairs, so they get two.
+#[cfg(not(feature = "loom"))]
+#[cfg_attr(any(target_arch = "x86_64", target_arch = "aarch64"), repr(align(128)))]
+#[cfg_attr(
+    not(any(target_arch = "x86_64", target_arch = "aarch64")),
+    repr(align(64))
+)]
+#[derive(Default)]
+pub(crate) struct CachePadded<T>(T);
+
+#[cfg(not(feature = "loom"))]
+impl<T> CachePadded<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+impl<T> core::ops::Deref for CachePadded<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Returns whether `counter` has advanced to at least `target`.
+///
+/// Compared by wrapping difference, so a counter that wraps around (in
+/// practice, the 32-bit one) stays correct while the two are less than half
+/// the counter's range apart.
+#[inline(always)]
+pub fn reached(counter: Count, target: Count) -> bool {
+    counter.wrapping_sub(target) <= Count::MAX / 2
+}
+
+/// Pauses like one iteration of the default busy phase.
+///
+/// Emits [`core::hint::spin_loop`] (`pause`/`yield`/`isb`, per platform),
+/// batched on x86 CPUs whose `pause` is short so the pause takes about as
+/// long everywhere. Useful in downstream spin loops.
+#[inline(always)]
+pub fn cpu_relax() {
+    crate::arch::relax(1);
+}
+
+/// Pauses like `n` iterations of the default busy phase; see [`cpu_relax`].
+#[inline]
+pub fn spin_hint_batch(n: u32) {
+    crate::arch::relax(n);
+}
+
+/// What each iteration of the busy phase executes between polls.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpinStrategy {
+    /// One [`core::hint::spin_loop`], or the `waitpkg`/`wfe` monitor wait
+    /// when that feature is enabled and supported. On x86, CPUs with a short
+    /// `pause` (pre-Skylake) batch several, as measured at first use.
+    #[default]
+    Pause,
+    /// No hint at all: poll back to back.
+    Nop,
+    /// Yield the time slice after every poll.
+    Yield,
+    /// The given number of [`core::hint::spin_loop`]s, for CPUs whose pause
+    /// is short.
+    Batched(u32),
+    /// Exponential backoff: double the [`core::hint::spin_loop`]s after each
+    /// poll, from 1 up to 64. Loads the counter's cache line less often under
+    /// contention while still catching quick signals early.
+    Backoff,
+}
+
 /// Tuning parameters used to configure the spinning behaviour of [`Waiter`].
+///
+/// With the `serde` feature, it (de)serializes as a map of its settings;
+/// settings missing on deserialization keep their [`Tuning::DEFAULT`] value.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Tuning {
     pub(crate) busy_iters: u32,
     pub(crate) yield_iters: u32,
+    pub(crate) spin: SpinStrategy,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub(crate) busy_cycles: Option<u64>,
+    #[cfg(feature = "std")]
+    pub(crate) busy_for: Option<Duration>,
+    #[cfg(feature = "std")]
+    pub(crate) yield_for: Option<Duration>,
+    #[cfg(feature = "std")]
+    pub(crate) sleep_iters: u32,
+    pub(crate) adaptive: bool,
+    pub(crate) burst: bool,
+    #[cfg(feature = "std")]
+    pub(crate) predictive: bool,
 }
 
 impl Tuning {
     /// Default tuning parameters, with a slight bias towards improved latency.
-    pub const DEFAULT: Tuning = Tuning {
-        busy_iters: 2_048,
-        yield_iters: 256,
-    };
+    pub const DEFAULT: Tuning = Tuning::new(2_048, 256);
+
+    /// Spins several times longer than [`DEFAULT`](Self::DEFAULT) before
+    /// yielding or parking, for waits that are usually short. Burns more CPU
+    /// when they aren't.
+    pub const LATENCY: Tuning = Tuning::new(16_384, 1_024);
+
+    /// Spins briefly and prefers yielding, leaving the core to other runnable
+    /// threads. Suited to oversubscribed machines where overall throughput
+    /// matters more than the latency of one wakeup.
+    pub const THROUGHPUT: Tuning = Tuning::new(256, 512);
+
+    /// Parks almost immediately, minimising CPU time and power draw at the
+    /// cost of a syscall-bound wakeup latency.
+    pub const POWER_SAVE: Tuning = Tuning::new(16, 0);
+
+    /// Spins much longer and never yields, for waiters pinned to a dedicated
+    /// core (e.g. under `SCHED_FIFO`), where a yield rarely helps and a park
+    /// costs the most. Occupies the core for the whole busy phase.
+    pub const REALTIME: Tuning = Tuning::new(1 << 20, 0);
+
+    /// [`DEFAULT`](Self::DEFAULT) budgets, adapted per waiter; see
+    /// [`adaptive`](Self::adaptive).
+    pub const ADAPTIVE: Tuning = Tuning::DEFAULT.adaptive(true);
 
     /// Create a custom tuning configuration.
     pub const fn new(busy_iters: u32, yield_iters: u32) -> Self {
         Self {
             busy_iters,
             yield_iters,
+            spin: SpinStrategy::Pause,
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            busy_cycles: None,
+            #[cfg(feature = "std")]
+            busy_for: None,
+            #[cfg(feature = "std")]
+            yield_for: None,
+            #[cfg(feature = "std")]
+            sleep_iters: 0,
+            adaptive: false,
+            burst: false,
+            #[cfg(feature = "std")]
+            predictive: false,
         }
     }
 
+    /// Let each waiter adapt the spin budgets to how long its waits take.
+    ///
+    /// The waiter keeps a moving average of how long signals take to arrive
+    /// (in spin iterations), and spins only about twice that, up to
+    /// [`busy_iters`](Self::busy_iters). When waits usually end up parking,
+    /// it skips spinning and yielding altogether, still using the full
+    /// budgets every few waits to notice when they get shorter again. Only
+    /// the iteration budgets adapt; time and cycle bounds are not.
+    pub const fn adaptive(mut self, adaptive: bool) -> Self {
+        self.adaptive = adaptive;
+        self
+    }
+
+    /// Let each waiter stretch its busy phase during bursts of signals.
+    ///
+    /// The waiter remembers which of its last 32 waits ended in the busy
+    /// phase. When most did, signals are arriving in a burst and the busy
+    /// phase is stretched fourfold; when few did, traffic has gone quiet and
+    /// it is shrunk fourfold. Combines with [`adaptive`](Self::adaptive).
+    pub const fn burst_adaptive(mut self, burst: bool) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    /// Let each waiter predict when periodic signals arrive.
+    ///
+    /// The waiter keeps a moving average of the time between its wakeups.
+    /// Once it has one, a wait first parks until about 50µs before the next
+    /// predicted signal, then runs the phases as usual, spinning through the
+    /// final stretch. Suits frame-paced producers: close to parking's power
+    /// draw, close to spinning's latency. A signal arriving early still
+    /// wakes the waiter right away. Timed waits don't park ahead.
+    #[cfg(feature = "std")]
+    pub const fn predictive(mut self, predictive: bool) -> Self {
+        self.predictive = predictive;
+        self
+    }
+
     /// Set the maximum number of the initial pure spin phase iterations.
     pub fn busy_iters(mut self, t: u32) -> Self {
         self.busy_iters = t;
@@ -34,6 +212,67 @@ impl Tuning {
         self.yield_iters = t;
         self
     }
+
+    /// Returns the maximum number of the initial pure spin phase iterations.
+    pub const fn get_busy_iters(&self) -> u32 {
+        self.busy_iters
+    }
+
+    /// Returns the maximum number of the spin-yield phase iterations.
+    pub const fn get_yield_iters(&self) -> u32 {
+        self.yield_iters
+    }
+
+    /// Set what each busy phase iteration executes between polls.
+    pub fn spin_strategy(mut self, s: SpinStrategy) -> Self {
+        self.spin = s;
+        self
+    }
+
+    /// Bound the initial pure spin phase by TSC cycles, taking precedence over
+    /// [`busy_for`](Self::busy_for) and [`busy_iters`](Self::busy_iters).
+    ///
+    /// Only honoured when the CPU reports an invariant TSC, whose rate is
+    /// unaffected by frequency scaling; otherwise the other bounds apply.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub fn busy_cycles(mut self, cycles: u64) -> Self {
+        self.busy_cycles = Some(cycles);
+        self
+    }
+
+    /// Bound the initial pure spin phase by wall-clock time instead of
+    /// [`busy_iters`](Self::busy_iters).
+    ///
+    /// The clock is only read every few iterations, so the phase may overrun
+    /// `d` slightly.
+    #[cfg(feature = "std")]
+    pub fn busy_for(mut self, d: Duration) -> Self {
+        self.busy_for = Some(d);
+        self
+    }
+
+    /// Bound the spin-yield phase by wall-clock time instead of
+    /// [`yield_iters`](Self::yield_iters).
+    ///
+    /// The clock is only read every few yields, so the phase may overrun `d`
+    /// slightly.
+    #[cfg(feature = "std")]
+    pub fn yield_for(mut self, d: Duration) -> Self {
+        self.yield_for = Some(d);
+        self
+    }
+
+    /// Set the number of progressive sleeps between the spin-yield phase and
+    /// parking (none by default).
+    ///
+    /// Sleeps start at 1µs and grow tenfold each time, up to 1ms. A signal
+    /// arriving mid-sleep is only observed once that sleep ends, so this
+    /// trades wakeup latency for fewer wakeups of the waiting core.
+    #[cfg(feature = "std")]
+    pub fn sleep_iters(mut self, t: u32) -> Self {
+        self.sleep_iters = t;
+        self
+    }
 }
 
 impl Default for Tuning {
@@ -42,47 +281,930 @@ impl Default for Tuning {
     }
 }
 
-/// Spins, yields, then blocks via `atomic_wait` until `f` returns `true`.
+/// A tuning shared by waiters and channels, which can be changed while they
+/// are in use.
+///
+/// Endpoints created with [`pair_with_shared_tuning`] or
+/// [`channel_with_shared_tuning`] check it at the start of every wait, so a
+/// [`set`](Self::set) takes effect from their next wait on, e.g. to flip a
+/// fleet of channels from [`Tuning::LATENCY`] to [`Tuning::POWER_SAVE`]
+/// during off-peak hours. Clones refer to the same tuning.
+///
+/// Each endpoint keeps its own copy and only compares a generation number
+/// with the shared one while it is unchanged, so waits don't contend on a
+/// lock.
+///
+/// # Example
+///
+/// ```
+/// use waitx::{SharedTuning, Tuning};
+///
+/// let shared = SharedTuning::new(Tuning::LATENCY);
+/// let (tx, rx) = waitx::channel_with_shared_tuning(&shared);
+///
+/// tx.send(1);
+/// assert_eq!(rx.recv(), 1);
+///
+/// shared.set(Tuning::POWER_SAVE);
+/// tx.send(2);
+/// assert_eq!(rx.recv(), 2);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct SharedTuning(alloc::sync::Arc<SharedTuningInner>);
+
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+struct SharedTuningInner {
+    /// Bumped by every [`SharedTuning::set`], after the tuning changed.
+    generation: core::sync::atomic::AtomicUsize,
+    tuning: std::sync::RwLock<Tuning>,
+}
+
+#[cfg(feature = "std")]
+impl SharedTuning {
+    /// Creates a shared tuning, initially `tuning`.
+    pub fn new(tuning: Tuning) -> Self {
+        Self(alloc::sync::Arc::new(SharedTuningInner {
+            generation: core::sync::atomic::AtomicUsize::new(0),
+            tuning: std::sync::RwLock::new(tuning),
+        }))
+    }
+
+    /// Replaces the tuning for all endpoints using it.
+    pub fn set(&self, tuning: Tuning) {
+        let mut current = self
+            .0
+            .tuning
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        *current = tuning;
+        self.0
+            .generation
+            .fetch_add(1, core::sync::atomic::Ordering::Release);
+    }
+
+    /// Returns the current tuning.
+    #[inline]
+    pub fn get(&self) -> Tuning {
+        *self
+            .0
+            .tuning
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    #[inline(always)]
+    fn generation(&self) -> usize {
+        self.0
+            .generation
+            .load(core::sync::atomic::Ordering::Acquire)
+    }
+}
+
+/// One endpoint's copy of a [`SharedTuning`], refreshed only when the
+/// shared tuning's generation has moved on.
+#[cfg(feature = "std")]
+pub(crate) struct CachedTuning {
+    shared: SharedTuning,
+    /// The generation the copy was taken at, and the copy.
+    cache: core::cell::UnsafeCell<Option<(usize, Tuning)>>,
+    /// Held while `cache` is accessed. Only contended if the endpoint is
+    /// misused from two threads at once, which then read `shared` directly.
+    busy: core::sync::atomic::AtomicBool,
+}
+
+// SAFETY: `cache` is only accessed while holding `busy`.
+#[cfg(feature = "std")]
+unsafe impl Sync for CachedTuning {}
+
+#[cfg(feature = "std")]
+impl CachedTuning {
+    pub(crate) fn new(shared: SharedTuning) -> Self {
+        Self {
+            shared,
+            cache: core::cell::UnsafeCell::new(None),
+            busy: core::sync::atomic::AtomicBool::new(false),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get(&self) -> Tuning {
+        use core::sync::atomic::Ordering;
+
+        let generation = self.shared.generation();
+        if self.busy.swap(true, Ordering::Acquire) {
+            return self.shared.get();
+        }
+        // SAFETY: `busy` grants exclusive access to the cache.
+        let cache = unsafe { &mut *self.cache.get() };
+        let tuning = match *cache {
+            Some((seen, tuning)) if seen == generation => tuning,
+            _ => {
+                // at least as new as `generation`; a newer one is picked up
+                // again by the next wait
+                let tuning = self.shared.get();
+                *cache = Some((generation, tuning));
+                tuning
+            }
+        };
+        self.busy.store(false, Ordering::Release);
+        tuning
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clone for CachedTuning {
+    fn clone(&self) -> Self {
+        Self::new(self.shared.clone())
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static THREAD_TUNING: core::cell::Cell<Option<Tuning>> = const { core::cell::Cell::new(None) };
+}
+
+/// Sets the tuning that [`Waiter::wait`] and the blocking channel operations
+/// use on the current thread, unless the endpoint was created with its own
+/// (see [`pair_with_tuning`]).
+///
+/// Lets e.g. threads pinned to isolated cores spin aggressively while pool
+/// threads park early. Threads start out with [`Tuning::DEFAULT`], without
+/// spin phases on single-CPU machines (see [`thread_tuning`]).
+#[cfg(feature = "std")]
+pub fn set_thread_tuning(tuning: Tuning) {
+    THREAD_TUNING.with(|t| t.set(Some(tuning)));
+}
+
+/// Returns the current thread's default tuning; see [`set_thread_tuning`].
+///
+/// Unless one was set, this is [`Tuning::DEFAULT`] with the busy and yield
+/// phases collapsed to zero on machines with a single available CPU, where
+/// spinning only delays the thread that would signal.
+#[inline]
+pub fn thread_tuning() -> Tuning {
+    #[cfg(feature = "std")]
+    if let Ok(tuning) = THREAD_TUNING.try_with(|t| {
+        t.get().unwrap_or_else(|| {
+            // resolve the default once per thread rather than every wait
+            let tuning = default_tuning();
+            t.set(Some(tuning));
+            tuning
+        })
+    }) {
+        return tuning;
+    }
+
+    Tuning::DEFAULT
+}
+
+/// [`Tuning::DEFAULT`], without spinning on a single CPU. The CPU count is
+/// read once, at first use.
+#[cfg(feature = "std")]
+fn default_tuning() -> Tuning {
+    const UNKNOWN: u8 = 0;
+    const SINGLE: u8 = 1;
+    const MULTI: u8 = 2;
+    use core::sync::atomic::{AtomicU8, Ordering};
+    static CPUS: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+    let mut cpus = CPUS.load(Ordering::Relaxed);
+    if cpus == UNKNOWN {
+        cpus = match std::thread::available_parallelism() {
+            Ok(n) if n.get() == 1 => SINGLE,
+            _ => MULTI,
+        };
+        CPUS.store(cpus, Ordering::Relaxed);
+    }
+
+    if cpus == SINGLE {
+        Tuning::DEFAULT.busy_iters(0).yield_iters(0)
+    } else {
+        Tuning::DEFAULT
+    }
+}
+
+/// Busy-phase permits; `usize::MAX` when unlimited.
+static MAX_SPINNERS: core::sync::atomic::AtomicUsize =
+    core::sync::atomic::AtomicUsize::new(usize::MAX);
+
+/// Waiters currently in their busy phase, while limited.
+#[cfg(not(feature = "loom"))]
+static SPINNERS: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
+
+/// Limits how many waiters in the process may be in their busy phase at
+/// once; `None` (the default) lifts the limit.
+///
+/// When dozens of waiters spin at the same time they can starve the threads
+/// that would signal them. Waiters beyond the limit skip straight to the
+/// yield phase, and from there to parking.
+pub fn set_max_spinners(max: Option<usize>) {
+    MAX_SPINNERS.store(
+        max.unwrap_or(usize::MAX),
+        core::sync::atomic::Ordering::Relaxed,
+    );
+}
+
+/// A busy-phase permit from the [`set_max_spinners`] budget.
+#[cfg(not(feature = "loom"))]
+struct SpinPermit(bool);
+
+#[cfg(not(feature = "loom"))]
+impl SpinPermit {
+    #[inline(always)]
+    fn acquire() -> Option<Self> {
+        let max = MAX_SPINNERS.load(Ordering::Relaxed);
+        if max == usize::MAX {
+            return Some(Self(false));
+        }
+        SPINNERS
+            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| Self(true))
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+impl Drop for SpinPermit {
+    #[inline(always)]
+    fn drop(&mut self) {
+        if self.0 {
+            SPINNERS.fetch_sub(1, Ordering::Release);
+        }
+    }
+}
+
+/// Spins, yields, optionally sleeps, then blocks via `atomic_wait` until `f`
+/// returns `true`.
 #[cfg(not(feature = "loom"))]
 #[inline]
-pub fn wait_until_with_tuning(mut f: impl FnMut() -> bool, wake: &AtomicU32, tuning: Tuning) {
-    let Tuning {
-        busy_iters,
-        yield_iters,
-    } = tuning;
+pub fn wait_until_with_tuning(f: impl FnMut() -> bool, wake: &AtomicU32, tuning: Tuning) {
+    wait_until_with_park(f, wake, tuning, crate::park::wait);
+}
 
+/// Spins, yields, optionally sleeps, then blocks via `park` until `f` returns
+/// `true`.
+///
+/// `park(wake, val)` must block while `wake` holds `val`, and may return
+/// spuriously.
+#[cfg(not(feature = "loom"))]
+#[inline]
+pub fn wait_until_with_park(
+    mut f: impl FnMut() -> bool,
+    wake: &AtomicU32,
+    tuning: Tuning,
+    park: impl Fn(&AtomicU32, u32),
+) {
     // phase 1: busy spin
-    for _ in 0..busy_iters {
-        if f() {
-            return;
-        }
-        std::hint::spin_loop();
+    if busy_phase(&mut f, wake, &tuning) {
+        return;
     }
 
     // phase 2: yield spin
-    for _ in 0..yield_iters {
-        if f() {
-            return;
-        }
-        thread::yield_now();
+    if yield_phase(&mut f, &tuning) {
+        return;
     }
 
-    // phase 3: futex / WaitOnAddress
+    // phase 3: progressive sleep
+    #[cfg(feature = "std")]
+    if sleep_phase(&mut f, &tuning) {
+        return;
+    }
+
+    // phase 4: park
     loop {
         let val = wake.load(Ordering::Acquire);
         if f() {
             return;
         }
-        crate::atomic_wait::wait(wake, val);
+        park(wake, val);
         if f() {
             return;
         }
     }
 }
 
+/// Observed length recorded for a wait that had to park.
+#[cfg(not(feature = "loom"))]
+const PARKED: u32 = 1 << 20;
+
+/// Busy iterations an adaptive waiter keeps even when waits are long.
+#[cfg(not(feature = "loom"))]
+const MIN_BUSY: u32 = 32;
+
+/// Spin iterations one yield is assumed to cost.
+#[cfg(not(feature = "loom"))]
+const YIELD_WEIGHT: u32 = 32;
+
+/// An adaptive waiter uses its full budgets once every this many waits.
+#[cfg(not(feature = "loom"))]
+const PROBE_EVERY: u32 = 16;
+
+/// Recent waits a burst-adaptive waiter remembers.
+#[cfg(not(feature = "loom"))]
+const BURST_WINDOW: u32 = 32;
+
+/// Spin-phase hits among the recent waits above which the busy phase is
+/// stretched, and below which it is shrunk.
+#[cfg(not(feature = "loom"))]
+const BURST_HIGH: u32 = 24;
+#[cfg(not(feature = "loom"))]
+const BURST_LOW: u32 = 8;
+
+/// Factor the busy phase is stretched or shrunk by.
+#[cfg(not(feature = "loom"))]
+const BURST_SCALE: u32 = 4;
+
+/// Per-waiter wait history for [`Tuning::adaptive`] and
+/// [`Tuning::burst_adaptive`].
+#[cfg(not(feature = "loom"))]
+#[derive(Default)]
+pub struct Adaptive {
+    /// Moving average of observed wait lengths, in spin iterations.
+    ewma: AtomicU32,
+    waits: AtomicU32,
+    /// One bit per recent wait, set if it ended in the busy phase.
+    hits: AtomicU32,
+}
+
+#[cfg(not(feature = "loom"))]
+impl Adaptive {
+    pub(crate) const fn new() -> Self {
+        Self {
+            ewma: AtomicU32::new(0),
+            waits: AtomicU32::new(0),
+            hits: AtomicU32::new(0),
+        }
+    }
+
+    /// Derives this wait's budgets from `base` and the history.
+    #[inline]
+    fn budget(&self, base: Tuning) -> Tuning {
+        let mut tuning = base;
+
+        if base.adaptive
+            && !self
+                .waits
+                .fetch_add(1, Ordering::Relaxed)
+                .is_multiple_of(PROBE_EVERY)
+        {
+            let ewma = self.ewma.load(Ordering::Relaxed);
+            tuning = if ewma >= PARKED / 2 {
+                base.busy_iters(MIN_BUSY.min(base.busy_iters))
+                    .yield_iters(0)
+            } else {
+                let busy = ewma.saturating_mul(2).saturating_add(MIN_BUSY);
+                base.busy_iters(busy.min(base.busy_iters))
+            };
+        }
+
+        if base.burst {
+            let hits = self.hits.load(Ordering::Relaxed).count_ones();
+            if hits >= BURST_HIGH {
+                tuning.busy_iters = tuning.busy_iters.saturating_mul(BURST_SCALE);
+            } else if hits <= BURST_LOW {
+                tuning.busy_iters = (tuning.busy_iters / BURST_SCALE).max(MIN_BUSY);
+            }
+        }
+
+        tuning
+    }
+
+    /// Records a wait that took `polls` polls under `tuning`.
+    ///
+    /// The wait's length is folded into the average with a weight of 1/8.
+    #[inline]
+    fn record(&self, tuning: Tuning, polls: u32) {
+        let Tuning {
+            busy_iters,
+            yield_iters,
+            ..
+        } = tuning;
+
+        if tuning.burst {
+            let hit = u32::from(polls <= busy_iters);
+            let hits = self.hits.load(Ordering::Relaxed);
+            let mask = u32::MAX >> (u32::BITS - BURST_WINDOW);
+            self.hits
+                .store(((hits << 1) | hit) & mask, Ordering::Relaxed);
+        }
+
+        if tuning.adaptive {
+            let observed = if polls <= busy_iters {
+                polls
+            } else if polls - busy_iters <= yield_iters {
+                busy_iters.saturating_add((polls - busy_iters).saturating_mul(YIELD_WEIGHT))
+            } else {
+                PARKED
+            }
+            .min(PARKED);
+            let ewma = self.ewma.load(Ordering::Relaxed);
+            self.ewma
+                .store(ewma - ewma / 8 + observed / 8, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Like [`wait_until_with_tuning`], but adapts `base` to the waits recorded
+/// in `history`, then records this one.
+#[cfg(not(feature = "loom"))]
+#[inline]
+pub fn wait_until_adaptive(
+    mut f: impl FnMut() -> bool,
+    wake: &AtomicU32,
+    base: Tuning,
+    history: &Adaptive,
+    park: impl Fn(&AtomicU32, u32),
+) {
+    let tuning = history.budget(base);
+    let mut polls = 0u32;
+    wait_until_with_park(
+        || {
+            polls = polls.saturating_add(1);
+            f()
+        },
+        wake,
+        tuning,
+        park,
+    );
+    history.record(tuning, polls);
+}
+
+/// Reports waiters that stay parked for too long; see
+/// [`Waiter::set_watchdog`].
+#[cfg(all(feature = "std", not(feature = "loom")))]
+pub(crate) struct Watchdog {
+    limit: Duration,
+    on_stall: Box<dyn Fn(Duration) + Send + Sync>,
+}
+
+#[cfg(all(feature = "std", not(feature = "loom")))]
+impl Watchdog {
+    pub(crate) fn new(limit: Duration, on_stall: Box<dyn Fn(Duration) + Send + Sync>) -> Self {
+        Self { limit, on_stall }
+    }
+
+    /// Returns a park function for one wait. It blocks like
+    /// [`crate::park::wait`], but calls `on_stall` with the time spent
+    /// parked each time another `limit` of it has passed.
+    pub(crate) fn park(&self) -> impl Fn(&AtomicU32, u32) + '_ {
+        let parked_at = core::cell::Cell::new(None::<std::time::Instant>);
+        let report_at = core::cell::Cell::new(self.limit);
+        move |wake, val| {
+            let start = parked_at.get().unwrap_or_else(|| {
+                let now = std::time::Instant::now();
+                parked_at.set(Some(now));
+                now
+            });
+            let elapsed = start.elapsed();
+            match report_at.get().checked_sub(elapsed) {
+                Some(left) if !left.is_zero() => crate::park::wait_timeout(wake, val, left),
+                _ => {
+                    (self.on_stall)(elapsed);
+                    report_at.set(elapsed.saturating_add(self.limit));
+                }
+            }
+        }
+    }
+}
+
+/// Nanoseconds since the first call, wrapping on 32-bit targets.
+#[cfg(all(feature = "std", not(feature = "loom")))]
+fn now_nanos() -> usize {
+    static EPOCH: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+    EPOCH
+        .get_or_init(std::time::Instant::now)
+        .elapsed()
+        .as_nanos() as usize
+}
+
+/// The time of the latest [`Waker::signal`], recorded only while a
+/// [`LatencySla`] is set.
+#[cfg(all(feature = "std", not(feature = "loom")))]
+#[derive(Default)]
+pub(crate) struct SignalStamp {
+    enabled: AtomicBool,
+    at: core::sync::atomic::AtomicUsize,
+}
+
+#[cfg(all(feature = "std", not(feature = "loom")))]
+impl SignalStamp {
+    pub(crate) const fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            at: core::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn stamp(&self) {
+        if self.enabled.load(Ordering::Relaxed) {
+            self.at.store(now_nanos(), Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn enable(&self) {
+        self.enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Time since the latest signal.
+    fn elapsed(&self) -> Duration {
+        let at = self.at.load(Ordering::Relaxed);
+        Duration::from_nanos(now_nanos().wrapping_sub(at) as u64)
+    }
+}
+
+/// Expected wake latency of a waiter; see [`Waiter::set_latency_sla`].
+#[cfg(all(feature = "std", not(feature = "loom")))]
+pub(crate) struct LatencySla {
+    limit: Duration,
+    breaches: core::sync::atomic::AtomicUsize,
+    on_breach: Box<dyn Fn(Duration) + Send + Sync>,
+}
+
+#[cfg(all(feature = "std", not(feature = "loom")))]
+impl LatencySla {
+    pub(crate) fn new(limit: Duration, on_breach: Box<dyn Fn(Duration) + Send + Sync>) -> Self {
+        Self {
+            limit,
+            breaches: Default::default(),
+            on_breach,
+        }
+    }
+
+    pub(crate) fn breaches(&self) -> u64 {
+        self.breaches.load(Ordering::Relaxed) as u64
+    }
+
+    /// Checks the latency of a wake by the signal recorded in `stamp`.
+    pub(crate) fn check(&self, stamp: &SignalStamp) {
+        let latency = stamp.elapsed();
+        if latency > self.limit {
+            self.breaches.fetch_add(1, Ordering::Relaxed);
+            (self.on_breach)(latency);
+        }
+    }
+}
+
+/// How long before a predicted signal a [`Tuning::predictive`] waiter stops
+/// parking.
+#[cfg(all(feature = "std", not(feature = "loom")))]
+const PRE_ARM_LEAD: Duration = Duration::from_micros(50);
+
+/// Wakeup inter-arrival history for [`Tuning::predictive`].
+#[cfg(all(feature = "std", not(feature = "loom")))]
+#[derive(Default)]
+pub(crate) struct Predictor {
+    /// Time of the latest wakeup, see [`now_nanos`].
+    last: core::sync::atomic::AtomicUsize,
+    /// Moving average of the time between wakeups, in nanoseconds.
+    period: core::sync::atomic::AtomicUsize,
+}
+
+#[cfg(all(feature = "std", not(feature = "loom")))]
+impl Predictor {
+    pub(crate) const fn new() -> Self {
+        Self {
+            last: core::sync::atomic::AtomicUsize::new(0),
+            period: core::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    /// Parks until shortly before the predicted next signal, or until `f`
+    /// returns `true`.
+    ///
+    /// `park(wake, val, timeout)` must block like
+    /// [`crate::park::wait_timeout`].
+    pub(crate) fn pre_arm(
+        &self,
+        f: &mut impl FnMut() -> bool,
+        wake: &AtomicU32,
+        park: impl Fn(&AtomicU32, u32, Duration),
+    ) {
+        let last = self.last.load(Ordering::Relaxed);
+        let period = self.period.load(Ordering::Relaxed);
+        if last == 0 || period == 0 {
+            return;
+        }
+        let wake_at = last
+            .wrapping_add(period)
+            .wrapping_sub(PRE_ARM_LEAD.as_nanos() as usize);
+        loop {
+            let val = wake.load(Ordering::Acquire);
+            if f() {
+                return;
+            }
+            let left = wake_at.wrapping_sub(now_nanos()) as isize;
+            if left <= 0 {
+                return;
+            }
+            park(wake, val, Duration::from_nanos(left as u64));
+        }
+    }
+
+    /// Records a wakeup at the current time.
+    ///
+    /// The interval is folded into the average with a weight of 1/8.
+    pub(crate) fn record(&self) {
+        let now = now_nanos();
+        let last = self.last.swap(now, Ordering::Relaxed);
+        if last == 0 {
+            return;
+        }
+        let interval = now.wrapping_sub(last);
+        let period = self.period.load(Ordering::Relaxed);
+        let period = if period == 0 {
+            interval
+        } else {
+            period - period / 8 + interval / 8
+        };
+        self.period.store(period, Ordering::Relaxed);
+    }
+}
+
+/// Polls between clock reads while waiting with a deadline.
+#[cfg(all(feature = "std", not(feature = "loom")))]
+const DEADLINE_CLOCK_POLLS: u32 = 16;
+
+/// The deadline of one timed wait.
+#[cfg(all(feature = "std", not(feature = "loom")))]
+pub(crate) struct Deadline {
+    /// `None` if the timeout is too long to represent.
+    at: Option<std::time::Instant>,
+    polls: core::cell::Cell<u32>,
+    expired: core::cell::Cell<bool>,
+}
+
+#[cfg(all(feature = "std", not(feature = "loom")))]
+impl Deadline {
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Self {
+            at: std::time::Instant::now().checked_add(timeout),
+            polls: Default::default(),
+            expired: Default::default(),
+        }
+    }
+
+    fn remaining(&self) -> Option<Duration> {
+        self.at
+            .map(|at| at.saturating_duration_since(std::time::Instant::now()))
+    }
+
+    /// Fits `tuning` into the time remaining: time bounds are shortened to
+    /// it, and sleeps that would run past it are dropped.
+    pub(crate) fn budget(&self, mut tuning: Tuning) -> Tuning {
+        let Some(remaining) = self.remaining() else {
+            return tuning;
+        };
+        tuning.busy_for = tuning.busy_for.map(|d| d.min(remaining));
+        tuning.yield_for = tuning.yield_for.map(|d| d.min(remaining));
+        // parking ahead could sleep past the deadline
+        tuning.predictive = false;
+
+        let (mut sleep, mut slept, mut sleeps) = (Duration::from_micros(1), Duration::ZERO, 0);
+        while sleeps < tuning.sleep_iters {
+            slept += sleep;
+            if slept > remaining {
+                break;
+            }
+            sleeps += 1;
+            sleep = (sleep * 10).min(MAX_SLEEP);
+        }
+        tuning.sleep_iters = sleeps;
+        tuning
+    }
+
+    /// Returns whether the deadline has passed, reading the clock only every
+    /// few polls.
+    pub(crate) fn poll(&self) -> bool {
+        let polls = self.polls.get().wrapping_add(1);
+        self.polls.set(polls);
+        if polls.is_multiple_of(DEADLINE_CLOCK_POLLS) && self.remaining() == Some(Duration::ZERO) {
+            self.expired.set(true);
+        }
+        self.expired.get()
+    }
+
+    /// Blocks like [`crate::park::wait`], but no longer than until the
+    /// deadline.
+    pub(crate) fn park(&self, wake: &AtomicU32, val: u32) {
+        match self.remaining() {
+            None => crate::park::wait(wake, val),
+            Some(Duration::ZERO) => self.expired.set(true),
+            Some(left) => crate::park::wait_timeout(wake, val, left),
+        }
+    }
+}
+
+/// Iterations between clock reads in a time-bounded busy phase.
+#[cfg(all(feature = "std", not(feature = "loom")))]
+const BUSY_CLOCK_ITERS: u32 = 64;
+
+/// Yields between clock reads in a time-bounded yield phase.
+#[cfg(all(feature = "std", not(feature = "loom")))]
+const YIELD_CLOCK_ITERS: u32 = 8;
+
+#[cfg(not(feature = "loom"))]
+#[inline(always)]
+fn busy_phase(f: &mut impl FnMut() -> bool, wake: &AtomicU32, tuning: &Tuning) -> bool {
+    let Some(_permit) = SpinPermit::acquire() else {
+        return false;
+    };
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    if let Some(done) = tuning
+        .busy_cycles
+        .and_then(|cycles| crate::arch::busy_wait_cycles(f, wake, cycles, tuning.spin))
+    {
+        return done;
+    }
+
+    #[cfg(feature = "std")]
+    if let Some(limit) = tuning.busy_for {
+        return spin_for(limit, BUSY_CLOCK_ITERS, |n| {
+            crate::arch::busy_wait(f, wake, n, tuning.spin)
+        });
+    }
+
+    crate::arch::busy_wait(f, wake, tuning.busy_iters, tuning.spin)
+}
+
+#[cfg(not(feature = "loom"))]
+#[inline(always)]
+fn yield_phase(f: &mut impl FnMut() -> bool, tuning: &Tuning) -> bool {
+    let mut step = |n| {
+        for _ in 0..n {
+            if f() {
+                return true;
+            }
+            crate::park::yield_now();
+        }
+        false
+    };
+
+    #[cfg(feature = "std")]
+    if let Some(limit) = tuning.yield_for {
+        return spin_for(limit, YIELD_CLOCK_ITERS, step);
+    }
+
+    step(tuning.yield_iters)
+}
+
+/// Longest sleep of the progressive sleep phase.
+#[cfg(all(feature = "std", not(feature = "loom")))]
+const MAX_SLEEP: Duration = Duration::from_millis(1);
+
+#[cfg(all(feature = "std", not(feature = "loom")))]
+#[inline(always)]
+fn sleep_phase(f: &mut impl FnMut() -> bool, tuning: &Tuning) -> bool {
+    let mut sleep = Duration::from_micros(1);
+    for _ in 0..tuning.sleep_iters {
+        if f() {
+            return true;
+        }
+        std::thread::sleep(sleep);
+        sleep = (sleep * 10).min(MAX_SLEEP);
+    }
+    false
+}
+
+/// Runs `step(chunk)` until it returns `true` or `limit` has elapsed.
+#[cfg(all(feature = "std", not(feature = "loom")))]
+#[inline]
+fn spin_for(limit: Duration, chunk: u32, mut step: impl FnMut(u32) -> bool) -> bool {
+    let start = std::time::Instant::now();
+    loop {
+        if step(chunk) {
+            return true;
+        }
+        if start.elapsed() >= limit {
+            return false;
+        }
+    }
+}
+
 #[cfg(not(feature = "loom"))]
 #[allow(unused)]
 #[inline(always)]
 pub fn wait_until(f: impl FnMut() -> bool, wake: &AtomicU32) {
     wait_until_with_tuning(f, wake, Tuning::DEFAULT);
 }
+
+/// Debug-build record of which thread is inside an operation on a channel
+/// endpoint or a [`StaticPair`](crate::StaticPair)'s waiting side.
+///
+/// Endpoints are `Sync`, so safe code can share one between threads, but two
+/// concurrent sends (or receives) race on the slot. Each operation tags the
+/// endpoint with its thread and panics if another thread holds it. The tag
+/// also remembers the last thread to run an operation, so a wait that only
+/// this thread's other endpoint could end is caught instead of blocking.
+/// How long [`ThreadTag::stuck_here`] gives another thread to pick up an
+/// endpoint the calling thread used last.
+#[cfg(all(debug_assertions, feature = "std", not(feature = "loom")))]
+const SELF_WAIT_GRACE: Duration = Duration::from_secs(1);
+
+#[cfg(all(debug_assertions, feature = "std", not(feature = "loom")))]
+pub(crate) struct ThreadTag {
+    owner: core::sync::atomic::AtomicUsize,
+    last: core::sync::atomic::AtomicUsize,
+}
+
+#[cfg(all(debug_assertions, feature = "std", not(feature = "loom")))]
+impl ThreadTag {
+    pub(crate) const fn new() -> Self {
+        Self {
+            owner: core::sync::atomic::AtomicUsize::new(0),
+            last: core::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    /// Identifies the calling thread; never 0.
+    #[inline]
+    fn current() -> usize {
+        std::thread_local! {
+            static TOKEN: u8 = const { 0 };
+        }
+        TOKEN.with(|token| token as *const u8 as usize)
+    }
+
+    /// Tags the endpoint with the calling thread until the guard is dropped.
+    /// Nested operations on the same thread are fine.
+    #[inline]
+    pub(crate) fn enter(&self, what: &str) -> UseGuard<'_> {
+        let me = Self::current();
+        match self.owner.compare_exchange(
+            0,
+            me,
+            core::sync::atomic::Ordering::Acquire,
+            core::sync::atomic::Ordering::Relaxed,
+        ) {
+            Ok(_) => {
+                self.last.store(me, core::sync::atomic::Ordering::Relaxed);
+                UseGuard(Some(&self.owner))
+            }
+            Err(owner) if owner == me => UseGuard(None),
+            Err(_) => panic!("waitx: {what} used from two threads at once"),
+        }
+    }
+
+    /// Returns whether a wait while `pending` holds could only be ended by
+    /// the calling thread using this endpoint.
+    ///
+    /// That's certain if the thread is in the middle of an operation. If it
+    /// merely ran the last one, the endpoint may have been handed to a thread
+    /// that hasn't used it yet, so this waits out [`SELF_WAIT_GRACE`] first.
+    #[cold]
+    pub(crate) fn stuck_here(&self, pending: impl Fn() -> bool) -> bool {
+        let me = Self::current();
+        if self.owner.load(core::sync::atomic::Ordering::Relaxed) == me {
+            return pending();
+        }
+        let deadline = std::time::Instant::now() + SELF_WAIT_GRACE;
+        while self.last.load(core::sync::atomic::Ordering::Relaxed) == me && pending() {
+            if std::time::Instant::now() >= deadline {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        false
+    }
+}
+
+#[cfg(all(debug_assertions, feature = "std", not(feature = "loom")))]
+pub(crate) struct UseGuard<'a>(Option<&'a core::sync::atomic::AtomicUsize>);
+
+#[cfg(all(debug_assertions, feature = "std", not(feature = "loom")))]
+impl Drop for UseGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(owner) = self.0 {
+            owner.store(0, core::sync::atomic::Ordering::Release);
+        }
+    }
+}
+
+/// Release-build stand-in for the debug tag, which records nothing.
+#[cfg(not(all(debug_assertions, feature = "std", not(feature = "loom"))))]
+pub(crate) struct ThreadTag;
+
+#[cfg(not(all(debug_assertions, feature = "std", not(feature = "loom"))))]
+impl ThreadTag {
+    pub(crate) const fn new() -> Self {
+        Self
+    }
+
+    #[inline(always)]
+    pub(crate) fn enter(&self, _what: &str) -> UseGuard<'_> {
+        UseGuard(core::marker::PhantomData)
+    }
+
+    #[inline(always)]
+    pub(crate) fn stuck_here(&self, _pending: impl Fn() -> bool) -> bool {
+        false
+    }
+}
+
+#[cfg(not(all(debug_assertions, feature = "std", not(feature = "loom"))))]
+pub(crate) struct UseGuard<'a>(core::marker::PhantomData<&'a ()>);
@@ -0,0 +1,61 @@
+#[cfg(all(test, not(feature = "loom")))]
+mod tests {
+    use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+    use std::thread;
+    use waitx::*;
+
+    static WAITS: AtomicUsize = AtomicUsize::new(0);
+    static WAKES: AtomicUsize = AtomicUsize::new(0);
+    static ISR_WAKES: AtomicUsize = AtomicUsize::new(0);
+
+    /// Yield-based hooks that count how often they are invoked.
+    struct Counting;
+
+    impl ParkHooks for Counting {
+        fn wait(&self, atomic: &AtomicU32, value: u32) {
+            WAITS.fetch_add(1, Ordering::Relaxed);
+            while atomic.load(Ordering::Acquire) == value {
+                thread::yield_now();
+            }
+        }
+
+        fn wake_one(&self, _atomic: &AtomicU32) {
+            WAKES.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn wake_from_isr(&self, _atomic: &AtomicU32) {
+            ISR_WAKES.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_park_hooks_drive_blocking() {
+        set_park_hooks(&Counting).unwrap();
+        assert!(set_park_hooks(&Counting).is_err());
+
+        let (waker, waiter) = pair();
+        let handle = thread::spawn(move || {
+            waiter.wait();
+            waiter
+        });
+        thread::sleep(std::time::Duration::from_millis(50));
+        waker.signal();
+        let waiter = handle.join().unwrap();
+
+        assert!(WAITS.load(Ordering::Relaxed) >= 1);
+        assert!(WAKES.load(Ordering::Relaxed) >= 1);
+
+        // no waiter is parked, so nothing needs waking
+        let wakes = WAKES.load(Ordering::Relaxed);
+        waker.signal();
+        assert_eq!(WAKES.load(Ordering::Relaxed), wakes);
+        assert!(waiter.try_wait());
+
+        // interrupt-context signals go through their own hook
+        let handle = thread::spawn(move || waiter.wait_park_only());
+        thread::sleep(std::time::Duration::from_millis(50));
+        waker.signal_from_isr();
+        handle.join().unwrap();
+        assert_eq!(ISR_WAKES.load(Ordering::Relaxed), 1);
+    }
+}
@@ -1,9 +1,11 @@
-#[cfg(all(test, not(feature = "loom")))]
+// the tests lean on `std`-only APIs such as tunings with sleeps and
+// pipelines, so a `no_std` build runs `park_hooks` only
+#[cfg(all(test, feature = "std", not(feature = "loom")))]
 mod tests {
     use rand::RngExt;
     use std::sync::{
         Arc,
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicU32, AtomicUsize, Ordering},
     };
     use std::thread;
     use std::time::Duration;
@@ -170,4 +172,1664 @@ mod tests {
         sender.join().unwrap();
         receiver.join().unwrap();
     }
+
+    #[test]
+    fn test_time_bounded_tuning() {
+        let (waker, waiter) = pair();
+        let tuning = Tuning::DEFAULT
+            .busy_for(Duration::from_micros(50))
+            .yield_for(Duration::from_micros(200));
+        let handle = thread::spawn(move || {
+            for _ in 0..10 {
+                waiter.wait_with(tuning);
+            }
+        });
+        for i in 0..10 {
+            thread::sleep(Duration::from_micros(i * 100));
+            waker.signal();
+        }
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_progressive_sleep() {
+        let (waker, waiter) = pair();
+        let handle = thread::spawn(move || {
+            waiter.wait_with(Tuning::POWER_SAVE.sleep_iters(4));
+            waiter.wait_with(Tuning::POWER_SAVE.sleep_iters(4));
+        });
+        waker.signal();
+        thread::sleep(Duration::from_millis(20));
+        waker.signal();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_tuning_presets() {
+        for tuning in [
+            Tuning::LATENCY,
+            Tuning::THROUGHPUT,
+            Tuning::POWER_SAVE,
+            Tuning::REALTIME,
+        ] {
+            let (waker, waiter) = pair();
+            let handle = thread::spawn(move || waiter.wait_with(tuning));
+            thread::sleep(Duration::from_millis(5));
+            waker.signal();
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_spin_hints() {
+        let flag = Arc::new(AtomicUsize::new(0));
+        let handle = {
+            let flag = flag.clone();
+            thread::spawn(move || flag.store(1, Ordering::Release))
+        };
+        while flag.load(Ordering::Acquire) == 0 {
+            cpu_relax();
+            spin_hint_batch(4);
+            thread::yield_now();
+        }
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_spin_strategies() {
+        for strategy in [
+            SpinStrategy::Pause,
+            SpinStrategy::Nop,
+            SpinStrategy::Yield,
+            SpinStrategy::Batched(4),
+            SpinStrategy::Backoff,
+        ] {
+            let (tx, rx) = channel_with_tuning::<usize>(Tuning::DEFAULT.spin_strategy(strategy));
+            let handle = thread::spawn(move || {
+                for i in 0..100 {
+                    tx.send(i);
+                }
+            });
+            for i in 0..100 {
+                assert_eq!(rx.recv(), i);
+            }
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_adaptive_tuning() {
+        let (waker, waiter) = pair();
+        let tuning = Tuning::ADAPTIVE.burst_adaptive(true);
+        let handle = thread::spawn(move || {
+            for _ in 0..64 {
+                waiter.wait_with(tuning);
+            }
+        });
+        // long waits first, so the waiter stops spinning, then rapid fire
+        for i in 0..64 {
+            if i < 32 {
+                thread::sleep(Duration::from_micros(500));
+            }
+            waker.signal();
+        }
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_stored_tuning() {
+        let (tx, rx) = channel_with_tuning::<usize>(Tuning::POWER_SAVE);
+        let handle = thread::spawn(move || {
+            for i in 0..100 {
+                tx.send(i);
+            }
+        });
+        for i in 0..100 {
+            assert_eq!(rx.recv(), i);
+        }
+        handle.join().unwrap();
+
+        let (waker, waiter) = pair_with_tuning(Tuning::LATENCY);
+        waker.signal();
+        waiter.wait();
+    }
+
+    #[test]
+    fn test_thread_tuning() {
+        let (tx, rx) = channel::<usize>();
+        let handle = thread::spawn(move || {
+            set_thread_tuning(Tuning::POWER_SAVE);
+            for i in 0..100 {
+                tx.send(i);
+            }
+        });
+        set_thread_tuning(Tuning::LATENCY);
+        for i in 0..100 {
+            assert_eq!(rx.recv(), i);
+        }
+        handle.join().unwrap();
+        set_thread_tuning(Tuning::DEFAULT);
+    }
+
+    #[test]
+    fn test_single_cpu_tuning() {
+        let single = thread::available_parallelism().map_or(true, |n| n.get() == 1);
+        let tuning = thread::spawn(thread_tuning).join().unwrap();
+        let spins = !format!("{tuning:?}").starts_with("Tuning { busy_iters: 0, yield_iters: 0,");
+        assert_eq!(spins, !single);
+
+        // an explicit tuning is kept as is
+        let tuning = thread::spawn(|| {
+            set_thread_tuning(Tuning::DEFAULT);
+            thread_tuning()
+        })
+        .join()
+        .unwrap();
+        assert!(format!("{tuning:?}").starts_with("Tuning { busy_iters: 2048,"));
+    }
+
+    #[test]
+    fn test_max_spinners() {
+        set_max_spinners(Some(1));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let (waker, waiter) = pair();
+                let handle = thread::spawn(move || waiter.wait_with(Tuning::LATENCY));
+                (waker, handle)
+            })
+            .collect();
+        thread::sleep(Duration::from_millis(10));
+        for (waker, handle) in handles {
+            waker.signal();
+            handle.join().unwrap();
+        }
+        set_max_spinners(None);
+    }
+
+    #[test]
+    fn test_predictive_tuning() {
+        let (waker, waiter) = pair();
+        let handle = thread::spawn(move || {
+            for _ in 0..20 {
+                waiter.wait_with(Tuning::DEFAULT.predictive(true));
+            }
+        });
+        for _ in 0..20 {
+            thread::sleep(Duration::from_millis(2));
+            waker.signal();
+        }
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_shared_tuning() {
+        let shared = SharedTuning::new(Tuning::LATENCY);
+        let (tx, rx) = channel_with_shared_tuning::<usize>(&shared);
+        let (waker, waiter) = pair_with_shared_tuning(&shared);
+        let handle = thread::spawn(move || {
+            for i in 0..100 {
+                assert_eq!(rx.recv(), i);
+            }
+            waiter.wait();
+        });
+        for i in 0..100 {
+            if i == 50 {
+                shared.set(Tuning::POWER_SAVE);
+            }
+            tx.send(i);
+        }
+        waker.signal();
+        handle.join().unwrap();
+        assert_eq!(
+            format!("{:?}", shared.get()),
+            format!("{:?}", Tuning::POWER_SAVE)
+        );
+    }
+
+    #[test]
+    fn test_shared_tuning_refresh() {
+        // yields until signaled, never parking
+        let shared = SharedTuning::new(Tuning::new(0, u32::MAX));
+        let (waker, waiter) = pair_with_shared_tuning(&shared);
+        let barrier = Arc::new(std::sync::Barrier::new(2));
+        let handle = thread::spawn({
+            let barrier = barrier.clone();
+            move || {
+                waiter.wait();
+                barrier.wait();
+                barrier.wait();
+                waiter.wait();
+            }
+        });
+        thread::sleep(Duration::from_millis(20));
+        assert!(!waker.waiter_is_parked());
+        waker.signal();
+
+        // the waiter has a copy of the old tuning by now, and must notice
+        // the change to park right away
+        barrier.wait();
+        shared.set(Tuning::new(0, 0));
+        barrier.wait();
+        let start = std::time::Instant::now();
+        while !waker.waiter_is_parked() {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(1));
+        }
+        waker.signal();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_signal_n() {
+        let (waker, waiter) = pair();
+        waker.signal_n(5);
+        waker.signal_n(0);
+        waiter.wait_many(3);
+        waiter.wait_many(0);
+        assert!(waiter.try_wait());
+        assert!(waiter.try_wait());
+        assert!(!waiter.try_wait());
+
+        let handle = thread::spawn(move || waiter.wait_many(10));
+        thread::sleep(Duration::from_millis(10));
+        waker.signal_n(4);
+        waker.signal_n(6);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_deferred_signal() {
+        let (waker, waiter) = pair();
+        {
+            let deferred = waker.defer();
+            deferred.signal();
+            deferred.signal();
+            assert_eq!(deferred.pending(), 2);
+            assert!(!waiter.try_wait());
+            deferred.flush();
+            assert_eq!(deferred.pending(), 0);
+            deferred.signal();
+        }
+        assert!(waiter.try_wait());
+        assert!(waiter.try_wait());
+        assert!(waiter.try_wait());
+        assert!(!waiter.try_wait());
+
+        let handle = thread::spawn(move || waiter.wait_many(3));
+        thread::sleep(Duration::from_millis(10));
+        let deferred = waker.defer();
+        for _ in 0..3 {
+            deferred.signal();
+        }
+        drop(deferred);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_coalescing() {
+        let (waker, waiter) = pair_coalescing();
+        for _ in 0..5 {
+            waker.signal();
+        }
+        assert!(waiter.try_wait());
+        assert!(!waiter.try_wait());
+
+        waker.signal_n(3);
+        waiter.wait();
+        assert!(!waiter.try_wait());
+
+        let handle = thread::spawn(move || {
+            waiter.wait();
+            waiter
+        });
+        thread::sleep(Duration::from_millis(10));
+        waker.signal();
+        let waiter = handle.join().unwrap();
+        assert!(!waiter.try_wait());
+    }
+
+    #[test]
+    fn test_max_pending() {
+        let (waker, waiter) = pair_with_max_pending(3);
+        waker.signal_n(1000);
+        for _ in 0..3 {
+            assert!(waiter.try_wait());
+        }
+        assert!(!waiter.try_wait());
+
+        waker.signal_n(2);
+        waiter.wait_many(2);
+        assert!(!waiter.try_wait());
+
+        let (waker, waiter) = pair_with_max_pending(0);
+        waker.signal_n(2);
+        assert!(waiter.try_wait());
+        assert!(!waiter.try_wait());
+    }
+
+    #[test]
+    fn test_recv_many() {
+        let (tx, rx) = channel();
+        let mut buf = Vec::new();
+        assert_eq!(rx.try_drain(&mut buf), 0);
+        assert_eq!(rx.recv_many(&mut buf, 0), 0);
+
+        tx.send(1);
+        assert_eq!(rx.try_drain(&mut buf), 1);
+        let handle = thread::spawn(move || {
+            for i in 2..=10 {
+                tx.send(i);
+            }
+        });
+        while buf.len() < 10 {
+            rx.recv_many(&mut buf, 4);
+        }
+        handle.join().unwrap();
+        assert_eq!(buf, (1..=10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_recv_ref() {
+        let (tx, rx) = channel();
+        tx.send(vec![1u8; 4096]);
+        {
+            let value = rx.recv_ref();
+            assert_eq!(value.len(), 4096);
+            assert!(tx.try_send(Vec::new()).is_err());
+        }
+        tx.send(vec![2]);
+        assert_eq!(*rx.recv_ref(), [2]);
+
+        let (tx, rx) = channel();
+        let value = Arc::new(());
+        tx.send(value.clone());
+        drop(rx.recv_ref());
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_send_with_recv_with() {
+        let (tx, rx) = channel::<[u64; 512]>();
+        unsafe {
+            tx.send_with(|slot| {
+                slot.write([7; 512]);
+            });
+        }
+        let sum = rx.recv_with(|value| {
+            // read in place: the slot is only released afterwards
+            assert!(tx.is_full());
+            value.iter().sum::<u64>()
+        });
+        assert_eq!(sum, 7 * 512);
+        assert!(tx.is_empty());
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| unsafe {
+            tx.send_with(|_| panic!("init failed"));
+        }));
+        assert!(result.is_err());
+        assert!(rx.try_recv().is_none());
+        tx.send([1; 512]);
+        assert_eq!(rx.recv()[0], 1);
+    }
+
+    #[test]
+    fn test_reserve() {
+        let (tx, rx) = channel();
+        let permit = tx.reserve();
+        assert!(tx.try_reserve().is_none());
+        permit.send(1);
+        assert!(tx.try_reserve().is_none());
+        assert_eq!(rx.recv(), 1);
+
+        drop(tx.reserve());
+        assert!(rx.try_recv().is_none());
+        tx.try_reserve().unwrap().send(2);
+        assert_eq!(rx.recv(), 2);
+    }
+
+    #[test]
+    fn test_loaned_channel() {
+        let (tx, rx) = loaned_channel([0u8; 8192]);
+        let handle = thread::spawn(move || {
+            for i in 1..=3 {
+                let mut slot = tx.loan();
+                slot.fill(i);
+                slot.commit();
+            }
+            let mut slot = tx.loan();
+            slot[0] = 9;
+            drop(slot);
+        });
+        for i in 1..=3 {
+            let msg = rx.recv();
+            assert!(msg.iter().all(|&b| b == i));
+        }
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_channel_pool() {
+        let pool = ChannelPool::new();
+        let (tx, rx) = pool.channel();
+        tx.send(Arc::new(()));
+        pool.recycle(tx, rx);
+        assert_eq!(pool.available(), 1);
+
+        let value = Arc::new(());
+        let (tx, rx) = pool.channel();
+        assert_eq!(pool.available(), 0);
+        assert!(rx.try_recv().is_none());
+        tx.send(value.clone());
+        assert_eq!(Arc::strong_count(&value), 2);
+        pool.recycle(tx, rx);
+        assert_eq!(Arc::strong_count(&value), 1);
+
+        let (tx, _rx) = pool.channel();
+        let (_tx, rx) = pool.channel();
+        pool.recycle(tx, rx);
+        assert_eq!(pool.available(), 0);
+    }
+
+    #[test]
+    fn test_oneshot_scoped() {
+        let mut buf = vec![0u8; 4];
+        {
+            let mut state = OneshotState::new();
+            let (tx, rx) = oneshot_scoped(&mut state);
+            thread::scope(|s| {
+                s.spawn(|| {
+                    thread::sleep(Duration::from_millis(10));
+                    tx.send(&mut buf[..]);
+                });
+                rx.recv()[0] = 1;
+            });
+        }
+        assert_eq!(buf, [1, 0, 0, 0]);
+
+        let mut state = OneshotState::new();
+        let (tx, mut rx) = oneshot_scoped(&mut state);
+        assert!(rx.try_recv().is_none());
+        tx.send("done");
+        assert_eq!(rx.try_recv(), Some("done"));
+        assert!(rx.try_recv().is_none());
+
+        // an unreceived value is dropped with the state
+        let value = Arc::new(());
+        let mut state = OneshotState::new();
+        let (tx, _rx) = oneshot_scoped(&mut state);
+        tx.send(value.clone());
+        drop(state);
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_static_pair() {
+        static EVENT: StaticPair = StaticPair::new();
+
+        let handle = thread::spawn(|| {
+            EVENT.waiter().wait();
+            EVENT.waiter().wait();
+        });
+        thread::sleep(Duration::from_millis(10));
+        EVENT.waker().signal_n(2);
+        handle.join().unwrap();
+        assert!(!EVENT.waiter().try_wait());
+
+        let waker = EVENT.waker();
+        waker.signal();
+        assert!(EVENT.waiter().wait_timeout(Duration::from_millis(10)));
+        assert!(!EVENT.waiter().wait_timeout(Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn test_custom_storage() {
+        static EVENT: Event = Event::new();
+        let (waker, waiter) = pair_in(&EVENT);
+        let handle = thread::spawn(move || waiter.wait_many(2));
+        waker.signal();
+        waker.clone().signal();
+        handle.join().unwrap();
+
+        let state = ChannelState::new();
+        let (tx, rx) = channel_in(&state);
+        thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..10 {
+                    tx.send(i);
+                }
+            });
+            for i in 0..10 {
+                assert_eq!(rx.recv(), i);
+            }
+        });
+    }
+
+    #[test]
+    fn test_channel_arena() {
+        let mut arena = ChannelArena::new(4);
+        assert_eq!(arena.len(), 4);
+
+        assert!(std::mem::align_of::<ChannelState<u8>>() >= 64);
+
+        let channels = arena.channels().collect::<Vec<_>>();
+        assert_eq!(channels.len(), 4);
+        channels[0].0.send(Arc::new(()));
+        drop(channels);
+
+        let value = Arc::new(());
+        let mut channels = arena.channels();
+        let (tx, rx) = channels.next().unwrap();
+        assert!(rx.try_recv().is_none());
+        tx.send(value.clone());
+        assert_eq!(rx.recv(), value);
+    }
+
+    #[test]
+    fn test_try_constructors() {
+        let (waker, waiter) = try_pair().unwrap();
+        let handle = thread::spawn(move || waiter.wait());
+        waker.clone().signal();
+        handle.join().unwrap();
+
+        let value = Arc::new(());
+        let (tx, rx) = try_channel().unwrap();
+        let handle = thread::spawn(move || {
+            drop(rx.recv());
+            rx
+        });
+        tx.send(value.clone());
+        let rx = handle.join().unwrap();
+        tx.send(value.clone());
+        drop((tx, rx));
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_receiver_iter() {
+        let (tx, rx) = channel();
+        assert_eq!(rx.try_iter().next(), None);
+        tx.send(0);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), [0]);
+
+        let handle = thread::spawn(move || {
+            for i in 1..=5 {
+                tx.send(i);
+            }
+        });
+        assert_eq!(rx.iter().take(5).collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_into_iter_extend() {
+        let (mut tx, rx) = channel();
+        let handle = thread::spawn(move || {
+            let mut sum = 0;
+            for i in &rx {
+                if i == 0 {
+                    break;
+                }
+                sum += i;
+            }
+            (sum, rx.into_iter().next())
+        });
+        tx.extend([1, 2, 3, 0, 7]);
+        assert_eq!(handle.join().unwrap(), (6, Some(7)));
+    }
+
+    #[test]
+    fn test_peek() {
+        let (tx, mut rx) = channel();
+        assert_eq!(rx.peek(), None);
+        tx.send(String::from("route"));
+        assert_eq!(rx.peek().map(String::as_str), Some("route"));
+        assert_eq!(rx.peek().map(String::as_str), Some("route"));
+        assert!(tx.try_send(String::new()).is_err());
+        assert_eq!(rx.recv(), "route");
+        assert_eq!(rx.peek(), None);
+    }
+
+    #[test]
+    fn test_channel_introspection() {
+        let (tx, rx) = channel();
+        assert!(tx.is_empty() && rx.is_empty());
+        assert_eq!((tx.len(), rx.len()), (0, 0));
+        assert_eq!((tx.capacity(), rx.capacity()), (1, 1));
+
+        tx.send(1);
+        assert!(tx.is_full() && rx.is_full());
+        assert_eq!((tx.len(), rx.len()), (1, 1));
+
+        rx.recv();
+        assert!(tx.is_empty() && rx.is_empty());
+    }
+
+    #[test]
+    fn test_pending() {
+        let (waker, waiter) = pair();
+        assert_eq!(waiter.pending(), 0);
+        waker.signal_n(3);
+        assert_eq!(waiter.pending(), 3);
+        waiter.wait();
+        assert_eq!(waiter.pending(), 2);
+        waiter.wait_many(2);
+        assert_eq!(waiter.pending(), 0);
+
+        let (waker, waiter) = pair_with_max_pending(2);
+        waker.signal_n(5);
+        assert_eq!(waiter.pending(), 2);
+    }
+
+    #[test]
+    fn test_drain() {
+        let (waker, waiter) = pair();
+        assert_eq!(waiter.drain(), 0);
+        waker.signal_n(4);
+        assert_eq!(waiter.drain(), 4);
+        assert!(!waiter.try_wait());
+
+        waker.signal();
+        waiter.wait();
+        assert_eq!(waiter.drain(), 0);
+
+        let (waker, waiter) = pair_with_max_pending(2);
+        waker.signal_n(9);
+        assert_eq!(waiter.drain(), 2);
+        assert_eq!(waiter.pending(), 0);
+    }
+
+    #[test]
+    fn test_try_wait_spin() {
+        let (waker, waiter) = pair();
+        assert!(!waiter.try_wait_spin(0));
+        assert!(!waiter.try_wait_spin(100));
+        waker.signal();
+        assert!(waiter.try_wait_spin(0));
+
+        let handle = thread::spawn(move || while !waiter.try_wait_spin(1_000) {});
+        waker.signal();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_is_signaled() {
+        let (waker, waiter) = pair();
+        assert!(!waiter.is_signaled());
+        waker.signal();
+        assert!(waiter.is_signaled());
+        assert!(waiter.is_signaled());
+        assert!(waiter.try_wait());
+        assert!(!waiter.is_signaled());
+    }
+
+    #[test]
+    fn test_signal_counters() {
+        let (waker, waiter) = pair();
+        assert_eq!((waker.signals_sent(), waiter.signals_consumed()), (0, 0));
+        waker.signal();
+        waker.clone().signal_n(2);
+        assert_eq!(waker.signals_sent(), 3);
+        waiter.wait();
+        assert_eq!(waiter.signals_consumed(), 1);
+        waiter.drain();
+        assert_eq!(waiter.signals_consumed(), 3);
+    }
+
+    #[test]
+    fn test_peer_waiting() {
+        let (waker, waiter) = pair();
+        assert!(!waker.waiter_is_parked());
+        let handle = thread::spawn(move || waiter.wait_park_only());
+        while !waker.waiter_is_parked() {
+            thread::yield_now();
+        }
+        waker.signal();
+        handle.join().unwrap();
+        assert!(!waker.waiter_is_parked());
+
+        let (tx, rx) = channel();
+        assert!(!tx.receiver_is_waiting());
+        let handle = thread::spawn(move || rx.recv());
+        while !tx.receiver_is_waiting() {
+            thread::yield_now();
+        }
+        tx.send(1);
+        assert_eq!(handle.join().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_pair_with_initial() {
+        let (waker, waiter) = pair_with_initial(2);
+        assert_eq!(waiter.pending(), 2);
+        waiter.wait();
+        waiter.wait();
+        assert!(!waiter.try_wait());
+        waker.signal();
+        waiter.wait();
+
+        static EVENT: Event = Event::with_initial(1);
+        let (_, waiter) = pair_in(&EVENT);
+        assert!(waiter.try_wait());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut event = Event::new();
+        for _ in 0..2 {
+            let (waker, waiter) = pair_in(&event);
+            assert!(!waiter.try_wait());
+            waker.signal();
+            waker.signal();
+            waiter.wait();
+            event.reset();
+        }
+
+        let value = Arc::new(());
+        let mut state = ChannelState::new();
+        for _ in 0..2 {
+            let (tx, rx) = channel_in(&state);
+            assert!(rx.try_recv().is_none());
+            tx.send(value.clone());
+            rx.recv();
+            // left behind, dropped by the reset
+            tx.send(value.clone());
+            drop((tx, rx));
+            state.reset();
+            assert_eq!(Arc::strong_count(&value), 1);
+        }
+    }
+
+    #[test]
+    fn test_skip() {
+        let (waker, waiter) = pair();
+        assert_eq!(waiter.skip(3), 0);
+        waker.signal_n(5);
+        assert_eq!(waiter.skip(3), 3);
+        assert_eq!(waiter.pending(), 2);
+        assert_eq!(waiter.skip(3), 2);
+        assert!(!waiter.try_wait());
+        waker.signal();
+        assert!(waiter.try_wait());
+    }
+
+    #[test]
+    fn test_wait_for_count() {
+        let (waker, waiter) = pair();
+        waiter.wait_for_count(0);
+        let handle = thread::spawn(move || {
+            waiter.wait_for_count(1000);
+            waiter
+        });
+        for _ in 0..1000 {
+            waker.signal();
+        }
+        let waiter = handle.join().unwrap();
+        assert_eq!(waiter.signals_consumed(), 0);
+        assert_eq!(waiter.pending(), 1000);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_concurrent_send_panics() {
+        let (tx, rx) = channel::<u8>();
+        tx.send(1);
+        thread::scope(|s| {
+            // blocks in `send` until the slot is emptied
+            let blocked = s.spawn(|| tx.send(2));
+            thread::sleep(Duration::from_millis(50));
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| tx.try_send(3)));
+            assert!(result.is_err());
+            assert_eq!(rx.recv(), 1);
+            blocked.join().unwrap();
+        });
+        assert_eq!(rx.recv(), 2);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_concurrent_static_wait_panics() {
+        static EVENT: StaticPair = StaticPair::new();
+
+        thread::scope(|s| {
+            // blocks in `wait` until signaled
+            let blocked = s.spawn(|| EVENT.waiter().wait());
+            thread::sleep(Duration::from_millis(50));
+            let result = std::panic::catch_unwind(|| EVENT.waiter().try_wait());
+            EVENT.waker().signal();
+            blocked.join().unwrap();
+            assert!(result.is_err());
+        });
+        EVENT.waker().signal();
+        assert!(EVENT.waiter().try_wait());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_self_wait_panics() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let (mut tx, rx) = channel::<u8>();
+        let result = catch_unwind(AssertUnwindSafe(|| tx.extend(rx.iter().take(1))));
+        assert!(result.is_err());
+        let result = catch_unwind(AssertUnwindSafe(|| unsafe {
+            tx.send_with(|slot| {
+                slot.write(rx.recv());
+            })
+        }));
+        assert!(result.is_err());
+
+        // the channel is still usable
+        tx.send(1);
+        assert_eq!(rx.recv(), 1);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_self_wait_after_send_panics() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let (tx, rx) = channel::<u8>();
+        tx.send(1);
+        assert_eq!(rx.recv(), 1);
+        tx.send(2);
+        assert_eq!(rx.recv(), 2);
+        // only this thread ever sends, so nothing could fill the slot
+        assert!(catch_unwind(AssertUnwindSafe(|| rx.recv())).is_err());
+
+        // handing the sender to a thread that sends is fine
+        let handle = thread::spawn(move || tx.send(3));
+        assert_eq!(rx.recv(), 3);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_self_wait_after_recv_panics() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let (tx, rx) = channel::<u8>();
+        tx.send(1);
+        assert_eq!(rx.recv(), 1);
+        tx.send(2);
+        // only this thread ever receives, so nothing could empty the slot
+        assert!(catch_unwind(AssertUnwindSafe(|| tx.send(3))).is_err());
+
+        // handing the receiver to a thread that receives is fine
+        let handle = thread::spawn(move || (rx.recv(), rx.recv()));
+        tx.send(3);
+        assert_eq!(handle.join().unwrap(), (2, 3));
+    }
+
+    #[test]
+    fn test_same_channel() {
+        let (tx1, rx1) = channel::<u8>();
+        let (tx2, rx2) = channel::<u8>();
+        assert!(tx1.same_channel(&rx1) && rx1.same_channel(&tx1));
+        assert!(!tx1.same_channel(&rx2) && !rx2.same_channel(&tx1));
+        assert!(tx2.same_channel(&rx2));
+
+        let (waker1, waiter1) = pair();
+        let (_, waiter2) = pair();
+        assert!(waker1.clone().same_pair(&waiter1) && waiter1.same_pair(&waker1));
+        assert!(!waker1.same_pair(&waiter2));
+    }
+
+    #[test]
+    fn test_close() {
+        let (tx, rx) = channel::<u8>();
+        tx.send(1);
+        tx.close();
+        assert!(rx.is_closed());
+        assert_eq!(tx.try_send(2), Err(2));
+        assert_eq!(tx.send_unless_closed(3), Err(3));
+        assert_eq!(rx.recv_unless_closed(), Ok(1));
+        assert!(rx.recv_unless_closed().is_err());
+        assert!(rx.try_recv().is_none());
+
+        // wakes a blocked receiver once the last value is taken
+        let (tx, rx) = channel::<u8>();
+        let handle = thread::spawn(move || rx.iter().count());
+        tx.send(1);
+        tx.send(2);
+        tx.close();
+        assert_eq!(handle.join().unwrap(), 2);
+
+        // wakes a blocked sender
+        let (tx, rx) = channel::<u8>();
+        tx.send(1);
+        let handle = thread::spawn(move || tx.send_unless_closed(2));
+        thread::sleep(Duration::from_millis(50));
+        rx.close();
+        assert_eq!(handle.join().unwrap(), Err(2));
+        assert_eq!(rx.try_recv(), Some(1));
+
+        // dropping either half closes the channel too
+        let (tx, rx) = channel::<u8>();
+        let handle = thread::spawn(move || rx.iter().collect::<Vec<_>>());
+        tx.send(1);
+        tx.send(2);
+        drop(tx);
+        assert_eq!(handle.join().unwrap(), [1, 2]);
+
+        let (tx, rx) = channel::<u8>();
+        tx.send(1);
+        let handle = thread::spawn(move || tx.send_unless_closed(2));
+        thread::sleep(Duration::from_millis(50));
+        drop(rx);
+        assert_eq!(handle.join().unwrap(), Err(2));
+    }
+
+    #[test]
+    fn test_receiver_into_inner() {
+        let (tx, rx) = channel::<u8>();
+        tx.send(7);
+        drop(tx);
+        assert_eq!(rx.into_inner(), Some(7));
+
+        let (_tx, rx) = channel::<u8>();
+        assert_eq!(rx.into_inner(), None);
+    }
+
+    #[test]
+    fn test_weak_handles() {
+        let (tx, rx) = channel::<u8>();
+        let weak = tx.downgrade();
+        // the sender is still in use, so the handle can't take over
+        assert!(weak.upgrade().is_none());
+        tx.send(1);
+        assert_eq!(rx.recv(), 1);
+        tx.send(2);
+        assert!(weak.upgrade().is_none());
+
+        // with a handle left, dropping the sender doesn't close the channel
+        drop(tx);
+        assert!(!rx.is_closed());
+        let tx = weak.upgrade().unwrap();
+        assert!(weak.upgrade().is_none());
+        assert!(tx.try_send(3).is_err());
+        assert_eq!(rx.recv(), 2);
+        tx.send(3);
+
+        let weak_rx = rx.downgrade();
+        drop(rx);
+        let rx = weak_rx.upgrade().unwrap();
+        assert_eq!(rx.recv(), 3);
+        assert!(rx.try_recv().is_none());
+
+        // dropping the other half ends the channel
+        drop(weak_rx);
+        drop(rx);
+        assert!(tx.is_closed());
+        drop(tx);
+        assert!(weak.upgrade().is_none());
+
+        // the last handle of a dropped sender closes the channel
+        let (tx, rx) = channel::<u8>();
+        let weak = tx.downgrade();
+        drop(tx);
+        let handle = thread::spawn(move || rx.recv_unless_closed());
+        thread::sleep(Duration::from_millis(10));
+        drop(weak);
+        assert!(handle.join().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_builder() {
+        let builder = Builder::new().tuning(Tuning::new(0, 0));
+        let (tx, rx) = builder.build::<u8>();
+        let handle = thread::spawn(move || rx.recv());
+        tx.send(1);
+        assert_eq!(handle.join().unwrap(), 1);
+
+        let (tx, rx) = builder.try_build::<u8>().unwrap();
+        tx.send(2);
+        assert_eq!(rx.recv(), 2);
+
+        let state = ChannelState::new();
+        let (tx, rx) = Builder::default().build_in(&state);
+        tx.send(3);
+        assert_eq!(rx.recv(), 3);
+    }
+
+    #[test]
+    fn test_channels_and_mesh() {
+        let pairs = channels::<usize>(4);
+        assert_eq!(pairs.len(), 4);
+        for (i, (tx, rx)) in pairs.iter().enumerate() {
+            tx.send(i);
+            assert_eq!(rx.recv(), i);
+        }
+
+        let nodes = mesh::<usize>(4);
+        let totals: Vec<usize> = thread::scope(|s| {
+            let handles: Vec<_> = nodes
+                .into_iter()
+                .map(|node| {
+                    s.spawn(move || {
+                        for peer in (0..node.len()).filter(|&peer| peer != node.id()) {
+                            node.sender(peer).unwrap().send(node.id());
+                        }
+                        (0..node.len())
+                            .filter_map(|peer| node.receiver(peer))
+                            .map(|rx| rx.recv())
+                            .sum()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        // every node receives the ids of the other three
+        assert_eq!(totals, [6, 5, 4, 3]);
+    }
+
+    #[test]
+    fn test_rpc() {
+        let (client, server) = rpc::channel::<String, usize>();
+        let handle = thread::spawn(move || {
+            let mut served = 0;
+            server.serve(|req| {
+                served += 1;
+                req.len()
+            });
+            served
+        });
+        for i in 0..100 {
+            assert_eq!(client.call("x".repeat(i)), i);
+        }
+        client.close();
+        assert_eq!(handle.join().unwrap(), 100);
+
+        // dropping the client stops the server as well
+        let (client, server) = rpc::channel::<u8, u8>();
+        let handle = thread::spawn(move || server.serve(|x| x));
+        assert_eq!(client.call(1), 1);
+        drop(client);
+        handle.join().unwrap();
+
+        // the client can move to another thread, just not be shared
+        let (client, server) = rpc::channel::<u8, u8>();
+        let handle = thread::spawn(move || server.serve(|x| x + 1));
+        let caller = thread::spawn(move || client.call(1));
+        assert_eq!(caller.join().unwrap(), 2);
+        handle.join().unwrap();
+
+        // a server dropped mid-call, here by its panicking handler, fails
+        // the call instead of leaving it blocked
+        let (client, server) = rpc::channel::<u8, u8>();
+        let handle = thread::spawn(move || server.serve(|_| panic!("handler failed")));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| client.call(1)));
+        assert!(result.is_err());
+        assert!(handle.join().is_err());
+    }
+
+    #[test]
+    fn test_session() {
+        let (client, server) = session::channel::<u32, u32>();
+        let handle = thread::spawn(move || {
+            let mut server = server;
+            for _ in 0..100 {
+                let (n, reply) = server.recv();
+                server = reply.send(n + 1);
+            }
+        });
+
+        let mut client = client;
+        for i in 0..100 {
+            let pending = client.send(i);
+            while !pending.is_ready() {
+                std::hint::spin_loop();
+            }
+            let (n, next) = pending.recv();
+            assert_eq!(n, i + 1);
+            client = next;
+        }
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let (tx, rx) = channel::<u32>();
+        let (out, workers) = pipeline::Pipeline::new(rx)
+            .stage(|x| x + 1)
+            .stage_with_tuning(Tuning::new(0, 0), |x| u64::from(x) * 2)
+            .into_parts();
+        let producer = thread::spawn(move || {
+            (0..100).for_each(|x| tx.send(x));
+            tx.close();
+        });
+        assert_eq!(out.iter().sum::<u64>(), 10_100);
+        producer.join().unwrap();
+        workers.into_iter().for_each(|w| w.join().unwrap());
+
+        // closing the output stops the stage and its input
+        let (tx, rx) = channel::<u32>();
+        let (out, worker) = pipeline::stage(rx, |x| x);
+        tx.send(1);
+        tx.send(2);
+        out.close();
+        worker.join().unwrap();
+        assert!(tx.is_closed());
+
+        // dropping the input stops every stage
+        let (tx, rx) = channel::<u32>();
+        let (out, workers) = pipeline::Pipeline::new(rx)
+            .stage(|x| x + 1)
+            .stage(|x| x * 2)
+            .into_parts();
+        tx.send(1);
+        drop(tx);
+        assert_eq!(out.iter().collect::<Vec<_>>(), [4]);
+        workers.into_iter().for_each(|w| w.join().unwrap());
+    }
+
+    #[test]
+    fn test_spawn_per_core() {
+        let cpus = [0, 0, 0];
+        let workers = topology::spawn_per_core(&cpus, |core: topology::Core<usize>| {
+            for peer in (0..core.len()).filter(|&peer| peer != core.id()) {
+                core.sender(peer).unwrap().send(core.id());
+            }
+            let sum: usize = (0..core.len())
+                .filter_map(|peer| core.receiver(peer))
+                .map(|rx| rx.recv())
+                .sum();
+            (core.cpu(), sum)
+        });
+        let got: Vec<_> = workers.into_iter().map(|w| w.join().unwrap()).collect();
+        assert_eq!(got, [(0, 3), (0, 2), (0, 1)]);
+    }
+
+    #[test]
+    fn test_aggregator() {
+        const PER_PRODUCER: usize = 1000;
+        let (producers, consumer) = aggregator::channel::<usize>(4);
+        assert_eq!(consumer.shards(), 4);
+        let handles: Vec<_> = producers
+            .into_iter()
+            .map(|producer| {
+                thread::spawn(move || {
+                    for i in 0..PER_PRODUCER {
+                        producer.send(producer.shard() * PER_PRODUCER + i);
+                    }
+                })
+            })
+            .collect();
+
+        // values arrive in order per shard
+        let mut next = [0; 4];
+        for _ in 0..4 * PER_PRODUCER {
+            let (shard, value) = consumer.recv();
+            assert_eq!(value, shard * PER_PRODUCER + next[shard]);
+            next[shard] += 1;
+        }
+        assert!(consumer.try_recv().is_none());
+        handles.into_iter().for_each(|h| h.join().unwrap());
+        assert!(consumer.recv_unless_closed().is_err());
+
+        // once every producer is dropped, what's left is still received
+        let (producers, consumer) = aggregator::channel::<u8>(2);
+        producers[1].send(1);
+        drop(producers);
+        assert!(consumer.is_closed());
+        assert_eq!(consumer.recv(), (1, 1));
+        assert!(consumer.recv_unless_closed().is_err());
+
+        // and a blocked consumer is woken
+        let (producers, consumer) = aggregator::channel::<u8>(1);
+        let handle = thread::spawn(move || consumer.recv_unless_closed());
+        thread::sleep(Duration::from_millis(10));
+        drop(producers);
+        assert!(handle.join().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_task_channel() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let (tasks, worker) = task_channel();
+        let mut ticket = tasks.submit(|| "early");
+        assert!(ticket.try_join().is_none());
+        assert!(worker.run_pending());
+        assert!(!worker.run_pending());
+        assert_eq!(ticket.try_join(), Some("early"));
+
+        let handle = thread::spawn(move || worker.run());
+        let tickets: Vec<_> = (0..100).map(|i| tasks.submit(move || i * 2)).collect();
+        for (i, ticket) in tickets.into_iter().enumerate() {
+            assert_eq!(ticket.join(), i * 2);
+        }
+        tasks.close();
+        handle.join().unwrap();
+
+        // dropping the sender stops the worker as well
+        let (tasks, worker) = task_channel();
+        let handle = thread::spawn(move || worker.run());
+        assert_eq!(tasks.submit(|| 1).join(), 1);
+        drop(tasks);
+        handle.join().unwrap();
+
+        // a task dropped with the worker, or panicking, fails its ticket
+        let (tasks, worker) = task_channel();
+        let ticket = tasks.submit(|| 1);
+        drop(worker);
+        assert!(catch_unwind(AssertUnwindSafe(|| ticket.join())).is_err());
+
+        let (tasks, worker) = task_channel();
+        let ticket = tasks.submit(|| -> u8 { panic!("task failed") });
+        assert!(catch_unwind(AssertUnwindSafe(|| worker.run_pending())).is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| ticket.join())).is_err());
+    }
+
+    #[test]
+    fn test_block_on() {
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::task::{Context, Poll};
+
+        /// Pending until a thread it spawned on first poll has woken it
+        /// `left` times.
+        struct Countdown {
+            left: Arc<AtomicUsize>,
+            started: bool,
+        }
+
+        impl Future for Countdown {
+            type Output = &'static str;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<&'static str> {
+                if self.left.load(Ordering::Acquire) == 0 {
+                    return Poll::Ready("done");
+                }
+                if !self.started {
+                    self.started = true;
+                    let left = self.left.clone();
+                    let waker = cx.waker().clone();
+                    thread::spawn(move || {
+                        while left.load(Ordering::Acquire) > 0 {
+                            thread::sleep(Duration::from_millis(1));
+                            left.fetch_sub(1, Ordering::Release);
+                            waker.wake_by_ref();
+                        }
+                    });
+                }
+                Poll::Pending
+            }
+        }
+
+        let left = Arc::new(AtomicUsize::new(5));
+        assert_eq!(
+            block_on(Countdown {
+                left,
+                started: false
+            }),
+            "done"
+        );
+        assert_eq!(block_on(async { 1 + 1 }), 2);
+    }
+
+    #[test]
+    fn test_park_only() {
+        let (tx, rx) = channel::<u8>();
+        let handle = thread::spawn(move || rx.recv_parking());
+        thread::sleep(Duration::from_millis(20));
+        tx.send(7);
+        assert_eq!(handle.join().unwrap(), 7);
+
+        let (waker, waiter) = pair();
+        waker.signal();
+        waiter.wait_park_only();
+    }
+
+    #[test]
+    fn test_watchdog() {
+        let stalls = Arc::new(AtomicU32::new(0));
+        let (waker, mut waiter) = pair();
+        waiter.set_watchdog(Duration::from_millis(10), {
+            let stalls = stalls.clone();
+            move |_| {
+                stalls.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        let handle = thread::spawn(move || waiter.wait_park_only());
+        thread::sleep(Duration::from_millis(50));
+        waker.signal();
+        handle.join().unwrap();
+        assert!(stalls.load(Ordering::Relaxed) >= 2);
+    }
+
+    #[test]
+    fn test_wait_timeout() {
+        let (waker, waiter) = pair();
+        assert!(!waiter.wait_timeout(Duration::from_micros(5)));
+        let start = std::time::Instant::now();
+        assert!(
+            !waiter.wait_timeout_with(Tuning::DEFAULT.sleep_iters(8), Duration::from_millis(20))
+        );
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        // timed-out waits don't consume notifications
+        waker.signal();
+        assert!(waiter.wait_timeout(Duration::from_secs(5)));
+        assert!(!waiter.try_wait());
+
+        let (tx, rx) = channel::<u8>();
+        assert_eq!(rx.recv_timeout(Duration::from_millis(1)), None);
+        let handle = thread::spawn(move || rx.recv_timeout(Duration::from_secs(5)));
+        thread::sleep(Duration::from_millis(10));
+        tx.send(3);
+        assert_eq!(handle.join().unwrap(), Some(3));
+    }
+
+    #[test]
+    fn test_latency_sla() {
+        let breaches = Arc::new(AtomicU32::new(0));
+        let (tx, mut rx) = channel::<u8>();
+        rx.set_latency_sla(Duration::ZERO, {
+            let breaches = breaches.clone();
+            move |latency| {
+                assert!(latency > Duration::ZERO);
+                breaches.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        let handle = thread::spawn(move || {
+            assert_eq!(rx.recv_parking(), 1);
+            rx.sla_breaches()
+        });
+        thread::sleep(Duration::from_millis(10));
+        tx.send(1);
+        assert_eq!(handle.join().unwrap(), 1);
+        assert_eq!(breaches.load(Ordering::Relaxed), 1);
+
+        let (waker, mut waiter) = pair();
+        waiter.set_latency_sla(Duration::from_secs(60), |_| panic!("breached"));
+        let handle = thread::spawn(move || {
+            waiter.wait();
+            waiter.sla_breaches()
+        });
+        thread::sleep(Duration::from_millis(10));
+        waker.signal();
+        assert_eq!(handle.join().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_calibrate() {
+        let tuning = calibrate();
+        let busy = tuning.get_busy_iters();
+        if thread::available_parallelism().unwrap().get() < 2 {
+            assert_eq!(busy, 16);
+        } else {
+            assert!((256..=65_536).contains(&busy), "busy_iters = {busy}");
+        }
+        assert_eq!(tuning.get_yield_iters(), Tuning::DEFAULT.get_yield_iters());
+
+        let (waker, waiter) = pair();
+        let handle = thread::spawn(move || waiter.wait_with(tuning));
+        waker.signal();
+        handle.join().unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    #[cfg_attr(miri, ignore = "Miri can't read sysfs")]
+    fn test_smt_aware_tuning() {
+        let default = Tuning::DEFAULT.get_busy_iters();
+        assert_eq!(shares_core(0, 0), Some(true));
+        assert_eq!(Tuning::DEFAULT.for_cpus(0, 0).get_busy_iters(), default / 8);
+
+        // distinct CPUs are looked up in sysfs; check against cpu0's siblings
+        let topology = |cpu: usize| format!("/sys/devices/system/cpu/cpu{cpu}/topology");
+        let siblings: Vec<usize> = std::fs::read_to_string(topology(0) + "/thread_siblings_list")
+            .unwrap_or_default()
+            .trim()
+            .split(',')
+            .filter(|range| !range.is_empty())
+            .flat_map(|range| {
+                let (lo, hi) = range.split_once('-').unwrap_or((range, range));
+                lo.parse().unwrap()..=hi.parse().unwrap()
+            })
+            .collect();
+        let known = |cpu: usize| std::path::Path::new(&(topology(cpu) + "/core_id")).exists();
+        for cpu in 1..64 {
+            let known = known(0) && known(cpu);
+            let shortened = Tuning::DEFAULT.for_cpus(0, cpu).get_busy_iters() != default;
+            match shares_core(0, cpu) {
+                None => assert!(!known && !shortened, "cpu{cpu}"),
+                Some(shared) => {
+                    assert!(known, "cpu{cpu}");
+                    assert_eq!(shared, siblings.contains(&cpu), "cpu{cpu}");
+                    assert_eq!(shortened, shared, "cpu{cpu}");
+                }
+            }
+        }
+
+        let (waker, waiter) = pair();
+        waker.signal();
+        waiter.wait_with(Tuning::DEFAULT.for_cpus(0, 0));
+    }
+
+    #[cfg(all(target_arch = "x86_64", not(miri)))]
+    #[test]
+    fn test_cycle_bounded_tuning() {
+        // without an invariant TSC the cycle budget is ignored by design
+        #[allow(unused_unsafe)]
+        let invariant = unsafe {
+            use std::arch::x86_64::__cpuid;
+            __cpuid(0x8000_0000).eax >= 0x8000_0007 && __cpuid(0x8000_0007).edx & (1 << 8) != 0
+        };
+        if !invariant {
+            return;
+        }
+
+        // the iteration budget alone would keep the waiter spinning for minutes
+        let tuning = Tuning::new(u32::MAX, 0).busy_cycles(100_000);
+        let (waker, waiter) = pair();
+        let handle = thread::spawn(move || waiter.wait_with(tuning));
+
+        let start = std::time::Instant::now();
+        while !waker.waiter_is_parked() {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "the busy phase outlived its cycle budget"
+            );
+            thread::sleep(Duration::from_millis(1));
+        }
+        waker.signal();
+        handle.join().unwrap();
+    }
+
+    #[cfg(all(feature = "eventfd", target_os = "linux"))]
+    #[test]
+    fn test_eventfd_readiness() {
+        use std::io::Read;
+
+        let (waker, waiter) = pair_with_eventfd().unwrap();
+        let fd = waiter.eventfd().unwrap().try_clone_to_owned().unwrap();
+        let mut file = std::fs::File::from(fd);
+        let mut buf = [0u8; 8];
+
+        // nothing signaled yet: the non-blocking read would block
+        assert!(file.read(&mut buf).is_err());
+
+        waker.signal();
+        waker.signal();
+        file.read_exact(&mut buf).unwrap();
+        assert_eq!(u64::from_ne_bytes(buf), 2);
+
+        // the interrupt-context path notifies the eventfd too
+        waker.signal_from_isr();
+        file.read_exact(&mut buf).unwrap();
+        assert_eq!(u64::from_ne_bytes(buf), 1);
+
+        assert!(waiter.try_wait());
+        assert!(waiter.try_wait());
+        assert!(waiter.try_wait());
+        assert!(!waiter.try_wait());
+    }
+
+    #[cfg(all(feature = "fd", unix))]
+    #[test]
+    fn test_pollable_fd() {
+        let (waker, waiter) = pair_with_fd().unwrap();
+        let fd = waiter.as_raw_fd().unwrap();
+        let mut pfd = libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        assert_eq!(unsafe { libc::poll(&mut pfd, 1, 0) }, 0);
+        waker.signal();
+        assert_eq!(unsafe { libc::poll(&mut pfd, 1, 1_000) }, 1);
+        assert!(waiter.try_wait());
+    }
+
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    #[test]
+    fn test_msg_ring_completion() {
+        use std::os::fd::AsRawFd;
+
+        let mut ring = io_uring::IoUring::new(8).unwrap();
+        let (waker, waiter) = pair_with_msg_ring(ring.as_raw_fd(), 0xfeed).unwrap();
+
+        waker.signal();
+        ring.submit_and_wait(1).unwrap();
+        let cqe = ring.completion().next().unwrap();
+        assert_eq!(cqe.user_data(), 0xfeed);
+        assert!(waiter.try_wait());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[cfg_attr(miri, ignore = "Miri can't install signal handlers")]
+    fn test_signal_from_signal_handler() {
+        static WAKER: std::sync::OnceLock<Waker> = std::sync::OnceLock::new();
+
+        extern "C" fn handler(_: libc::c_int) {
+            if let Some(waker) = WAKER.get() {
+                waker.signal_from_signal_handler();
+            }
+        }
+
+        let (waker, waiter) = pair();
+        assert!(WAKER.set(waker).is_ok());
+        unsafe {
+            libc::signal(libc::SIGUSR1, handler as *const () as libc::sighandler_t);
+            libc::raise(libc::SIGUSR1);
+        }
+        waiter.wait();
+    }
+
+    #[cfg(all(feature = "signal-hook", unix))]
+    #[test]
+    fn test_signal_on_wakes_waiter() {
+        let (waker, waiter) = pair();
+        let guard = signal::signal_on(waker, &[libc::SIGUSR2]).unwrap();
+        unsafe { libc::raise(libc::SIGUSR2) };
+        waiter.wait();
+        drop(guard);
+    }
+
+    #[cfg(all(feature = "ipc", target_os = "linux"))]
+    #[test]
+    fn test_ipc_across_fork() {
+        use std::ptr::NonNull;
+
+        let layout = ipc::layout::<u64>();
+        let mem = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                layout.size(),
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        assert_ne!(mem, libc::MAP_FAILED);
+        let mem = NonNull::new(mem.cast::<u8>()).unwrap();
+        unsafe { ipc::init::<u64>(mem) };
+
+        match unsafe { libc::fork() } {
+            0 => {
+                // child: only async-signal-safe work until `_exit`
+                let tx = unsafe { ipc::sender::<u64>(mem, "waitx-test") }.unwrap();
+                for i in 0..1_000 {
+                    tx.send(i);
+                }
+                unsafe { libc::_exit(0) };
+            }
+            pid => {
+                assert!(pid > 0);
+                let rx = unsafe { ipc::receiver::<u64>(mem, "waitx-test") }.unwrap();
+                for i in 0..1_000 {
+                    assert_eq!(rx.recv(), i);
+                }
+                let mut status = 0;
+                assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+                assert_eq!(status, 0);
+                unsafe { libc::munmap(mem.as_ptr().cast(), layout.size()) };
+            }
+        }
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_channel_and_pair() {
+        use std::ffi::c_void;
+        use waitx::ffi::*;
+
+        unsafe {
+            let (mut tx, mut rx) = (std::ptr::null_mut(), std::ptr::null_mut());
+            waitx_channel_new(&mut tx, &mut rx);
+            let mut value = 7u32;
+            let ptr = (&raw mut value).cast::<c_void>();
+            waitx_send(tx, ptr);
+            assert!(!waitx_try_send(tx, ptr));
+            assert_eq!(waitx_recv(rx), ptr);
+            let mut out = std::ptr::null_mut();
+            assert!(!waitx_try_recv(rx, &mut out));
+            waitx_sender_free(tx);
+            waitx_receiver_free(rx);
+
+            // freeing or closing one side fails the other's calls
+            waitx_channel_new(&mut tx, &mut rx);
+            assert!(waitx_send_unless_closed(tx, ptr));
+            waitx_sender_free(tx);
+            assert!(waitx_recv_unless_closed(rx, &mut out));
+            assert_eq!(out, ptr);
+            assert!(!waitx_recv_unless_closed(rx, &mut out));
+            waitx_receiver_free(rx);
+            waitx_channel_new(&mut tx, &mut rx);
+            waitx_receiver_close(rx);
+            assert!(!waitx_send_unless_closed(tx, ptr));
+            waitx_sender_close(tx);
+            waitx_sender_free(tx);
+            waitx_receiver_free(rx);
+
+            let (mut waker, mut waiter) = (std::ptr::null_mut(), std::ptr::null_mut());
+            waitx_pair_new(&mut waker, &mut waiter);
+            let clone = waitx_waker_clone(waker);
+            waitx_waker_signal(clone);
+            waitx_waiter_wait(waiter);
+            assert!(!waitx_waiter_try_wait(waiter));
+            waitx_waker_free(clone);
+            waitx_waker_free(waker);
+            waitx_waiter_free(waiter);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tuning_serde() {
+        let tuning: Tuning =
+            serde_json::from_str(r#"{"busy_iters": 100, "spin": {"Batched": 4}}"#).unwrap();
+        let json = serde_json::to_value(tuning).unwrap();
+        assert_eq!(json["busy_iters"], 100);
+        assert_eq!(json["yield_iters"], 256);
+        assert_eq!(json["spin"]["Batched"], 4);
+
+        let (waker, waiter) = pair_with_tuning(tuning);
+        waker.signal();
+        waiter.wait();
+    }
 }